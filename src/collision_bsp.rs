@@ -1,8 +1,10 @@
 //! Provides functions for traversing BSPs.
 
+use alloc::vec::Vec;
+use core::mem::offset_of;
 use tinyvec::ArrayVec;
 use crate::float::FloatOps;
-use crate::vector::{Plane2D, Plane3D, Vector2D, Vector3D, Vector3DComponent};
+use crate::vector::{Cube3D, Plane2D, Plane3D, Vector2D, Vector3D, Vector3DComponent};
 
 /// Functions for traversing BSPs.
 ///
@@ -74,27 +76,32 @@ pub trait CollisionBSPFunctions {
     /// - `Ok(None)` if the point is outside the BSP
     /// - `Err(_)` if the BSP is malformed
     fn leaf_index_for_point_3d(&self, point: Vector3D) -> Result<Option<usize>, CollisionBSPError> {
-        let mut index = 0usize;
-
-        for _ in 0..self.get_3d_node_count().max(1) {
-            let node = checked_get_bsp_3d_node(self, index)?;
-            let plane = checked_get_bsp_plane(self, node.plane_index)?;
+        let root = checked_get_bsp_3d_node(self, 0)?;
+        let root_plane = checked_get_bsp_plane(self, root.plane_index)?;
+        leaf_index_for_point_3d_from_root(self, point, root, root_plane)
+    }
 
-            let next = if plane.distance_to_point(point) >= 0.0 {
-                node.front_child
-            }
-            else {
-                node.back_child
-            };
+    /// Return the leaf index for each point, writing them to `out`.
+    ///
+    /// This is the same as calling [`leaf_index_for_point_3d`](Self::leaf_index_for_point_3d) for
+    /// each point, but the root node is only fetched once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` and `out` are not the same length.
+    fn leaf_indices_for_points(&self, points: &[Vector3D], out: &mut [Option<usize>]) -> Result<(), CollisionBSPError> {
+        assert_eq!(points.len(), out.len(), "points and out must be the same length");
+        if points.is_empty() {
+            return Ok(())
+        }
 
-            match next.as_tuple() {
-                Some((CollisionBSP3DNodeIndexType::Node, next_index)) => index = next_index,
-                Some((CollisionBSP3DNodeIndexType::Leaf, leaf)) => return Ok(Some(leaf)),
-                None => return Ok(None)
-            }
+        let root = checked_get_bsp_3d_node(self, 0)?;
+        let root_plane = checked_get_bsp_plane(self, root.plane_index)?;
+        for (point, out) in points.iter().zip(out.iter_mut()) {
+            *out = leaf_index_for_point_3d_from_root(self, *point, root, root_plane)?;
         }
 
-        Err(CollisionBSPError::BSP3DNodeLoop(index))
+        Ok(())
     }
 
     /// Get the leaf index.
@@ -118,6 +125,9 @@ pub trait CollisionBSPFunctions {
     /// Check the BSP for out-of-bounds errors.
     ///
     /// Returns `Ok(())` if no errors are detected and `Err(_)` if an error was found.
+    ///
+    /// Each 2D BSP must also be a tree; a 2D node that can be reached more than once from the same
+    /// 2D node reference is reported as [`CollisionBSPError::BSP2DNodeLoop`].
     fn bounds_check(&self) -> Result<(), CollisionBSPError> {
         let bsp_3d_node_count = self.get_3d_node_count();
 
@@ -141,7 +151,7 @@ pub trait CollisionBSPFunctions {
             let _plane = checked_get_bsp_plane(self, p)?;
         }
 
-        for l in 0..=self.get_leaf_count() {
+        for l in 0..self.get_leaf_count() {
             let leaf = checked_get_bsp_leaf(self, l)?;
 
             let end = leaf
@@ -157,12 +167,7 @@ pub trait CollisionBSPFunctions {
         for r in 0..self.get_2d_node_reference_count() {
             let reference = checked_get_bsp_2d_node_reference(self, r)?;
             let _plane = checked_get_bsp_plane(self, reference.plane)?;
-
-            let _ = match reference.node.as_tuple() {
-                Some((CollisionBSP2DNodeIndexType::Node, n)) => checked_get_bsp_2d_node(self, n).map(|_| ())?,
-                Some((CollisionBSP2DNodeIndexType::Surface, _)) => return Err(CollisionBSPError::Bad2DReference(r)),
-                None => ()
-            };
+            walk_2d_reference(self, r, |_| ())?;
         }
 
         for node in 0..self.get_2d_node_count() {
@@ -200,6 +205,127 @@ pub trait CollisionBSPFunctions {
         Ok(())
     }
 
+    /// Group surfaces by the plane they lie on.
+    ///
+    /// Two surfaces are in the same group if their planes are [coincident](Plane3D::is_coincident),
+    /// even if they reference different plane indices. Surfaces on planes facing opposite
+    /// directions are not grouped together.
+    ///
+    /// Groups are ordered by their lowest surface index, and surface indices within each group are
+    /// in ascending order.
+    fn coplanar_surface_groups(&self) -> Result<Vec<Vec<usize>>, CollisionBSPError> {
+        let mut groups: Vec<(Plane3D, Vec<usize>)> = Vec::new();
+
+        for s in 0..self.get_surface_count() {
+            let surface = checked_get_bsp_surface(self, s)?;
+            let plane = checked_get_bsp_plane(self, surface.plane)?;
+
+            match groups.iter_mut().find(|(p, _)| p.is_coincident(plane)) {
+                Some((_, surfaces)) => surfaces.push(s),
+                None => groups.push((plane, alloc::vec![s]))
+            }
+        }
+
+        Ok(groups.into_iter().map(|(_, surfaces)| surfaces).collect())
+    }
+
+    /// Get all surfaces in a leaf.
+    ///
+    /// This descends every 2D BSP referenced by the leaf. Each surface index is only returned once,
+    /// in ascending order.
+    fn surfaces_in_leaf(&self, leaf: usize) -> Result<impl Iterator<Item = usize>, CollisionBSPError> {
+        Ok(leaf_surfaces(self, leaf)?.into_iter())
+    }
+
+    /// Get all planes used by the surfaces in a leaf.
+    ///
+    /// Each plane index is only returned once, in ascending order.
+    fn leaf_planes(&self, leaf: usize) -> Result<impl Iterator<Item = (usize, Plane3D)>, CollisionBSPError> {
+        let mut planes: Vec<(usize, Plane3D)> = Vec::new();
+
+        for s in self.surfaces_in_leaf(leaf)? {
+            let surface = checked_get_bsp_surface(self, s)?;
+            if let Err(i) = planes.binary_search_by_key(&surface.plane, |(p, _)| *p) {
+                planes.insert(i, (surface.plane, checked_get_bsp_plane(self, surface.plane)?));
+            }
+        }
+
+        Ok(planes.into_iter())
+    }
+
+    /// Get the bounding box of a leaf.
+    ///
+    /// This is an approximation made from the vertices of all surfaces in the leaf. Surfaces may
+    /// extend past the leaf, so the result may be larger than the leaf itself.
+    ///
+    /// Returns `Ok(None)` if the leaf has no surfaces.
+    fn leaf_bounding_box(&self, leaf: usize) -> Result<Option<Cube3D>, CollisionBSPError> {
+        let mut points = Vec::new();
+        for s in self.surfaces_in_leaf(leaf)? {
+            points.extend(surface_vertices(self, s)?);
+        }
+        Ok(Cube3D::from_points(points))
+    }
+
+    /// Get all leaves that neighbor a leaf across one of its surfaces.
+    ///
+    /// For each surface in the leaf, the average of its vertices is offset 0.01 world units along
+    /// both sides of the surface's plane, and the leaf containing each point (if any) is found. As
+    /// such, this is expensive, and it may miss leaves that are thinner than 0.01 world units or
+    /// that only touch a surface away from its center.
+    ///
+    /// Each leaf index is only returned once, in ascending order, and `leaf` itself is excluded.
+    fn adjacent_leaves(&self, leaf: usize) -> Result<impl Iterator<Item = usize>, CollisionBSPError> {
+        const SAMPLE_DISTANCE: f32 = 0.01;
+
+        let mut leaves = Vec::new();
+        for s in self.surfaces_in_leaf(leaf)? {
+            let surface = checked_get_bsp_surface(self, s)?;
+            let plane = checked_get_bsp_plane(self, surface.plane)?;
+            let vertices = surface_vertices(self, s)?;
+
+            let center = vertices.iter().fold(Vector3D::ZEROED, |sum, v| sum + *v).scaled(1.0 / vertices.len() as f32);
+            for offset in [SAMPLE_DISTANCE, -SAMPLE_DISTANCE] {
+                let Some(other) = self.leaf_index_for_point_3d(center.apply_offset(plane.vector, offset))? else {
+                    continue
+                };
+                if other == leaf {
+                    continue
+                }
+                if let Err(i) = leaves.binary_search(&other) {
+                    leaves.insert(i, other);
+                }
+            }
+        }
+
+        Ok(leaves.into_iter())
+    }
+
+    /// Find the first surface hit when casting from `point` to `point + direction`.
+    ///
+    /// `direction` does not need to be normalized, as its length is the maximum distance of the
+    /// cast. The returned distance is in the same units as `direction` (i.e. world units).
+    ///
+    /// This uses [`test_vector`](Self::test_vector). Front-facing surfaces (i.e. those that go
+    /// from empty space into solid space) are always hit, and back-facing surfaces are hit unless
+    /// [`BSPQueryOptions::ignore_backfaces`] is set. Two-sided surfaces between leaves that
+    /// [contain double-sided surfaces](CollisionBSPLeaf::contains_double_sided_surfaces) are hit
+    /// from either side.
+    ///
+    /// If `point` is outside the BSP, it is in solid space, so the surface it leaves through is
+    /// back-facing.
+    ///
+    /// Returns `Ok(None)` if nothing was hit.
+    fn nearest_surface_along(&self, point: Vector3D, direction: Vector3D, options: BSPQueryOptions) -> Result<Option<(usize, f32)>, CollisionBSPError> {
+        let flags = CollisionBSPTestVectorFlags {
+            test_front_facing_surfaces: true,
+            test_back_facing_surfaces: !options.ignore_backfaces,
+            ..Default::default()
+        };
+        let result = self.test_vector(flags, &[], point, direction, 1.0)?;
+        Ok(result.hit_surface.map(|hit| (hit.surface_index, result.relative_distance * direction.magnitude())))
+    }
+
     /// Find a point where a line intersects on a BSP if it does.
     ///
     /// ## Remarks
@@ -260,6 +386,113 @@ wrap_getter!(checked_get_bsp_surface, get_surface, CollisionBSPSurface, MissingS
 wrap_getter!(checked_get_bsp_edge, get_edge, CollisionBSPEdge, MissingEdge);
 wrap_getter!(checked_get_bsp_vertex, get_vertex, CollisionBSPVertex, MissingVertex);
 
+fn leaf_index_for_point_3d_from_root<BSP: CollisionBSPFunctions + ?Sized>(
+    bsp: &BSP,
+    point: Vector3D,
+    root: CollisionBSP3DNode,
+    root_plane: Plane3D
+) -> Result<Option<usize>, CollisionBSPError> {
+    let mut index = 0usize;
+    let mut node = root;
+    let mut plane = root_plane;
+
+    for _ in 0..bsp.get_3d_node_count().max(1) {
+        let next = if plane.distance_to_point(point) >= 0.0 {
+            node.front_child
+        }
+        else {
+            node.back_child
+        };
+
+        match next.as_tuple() {
+            Some((CollisionBSP3DNodeIndexType::Node, next_index)) => index = next_index,
+            Some((CollisionBSP3DNodeIndexType::Leaf, leaf)) => return Ok(Some(leaf)),
+            None => return Ok(None)
+        }
+
+        node = checked_get_bsp_3d_node(bsp, index)?;
+        plane = checked_get_bsp_plane(bsp, node.plane_index)?;
+    }
+
+    Err(CollisionBSPError::BSP3DNodeLoop(index))
+}
+
+/// Walk the 2D BSP of a 2D node reference depth-first, calling `on_surface` for each surface in it.
+///
+/// The reference must point to a 2D node (or be null), or else [`CollisionBSPError::Bad2DReference`]
+/// is returned. Each 2D BSP must be a tree, so any node reached more than once, whether from a loop
+/// or a shared subtree, results in [`CollisionBSPError::BSP2DNodeLoop`].
+fn walk_2d_reference<BSP: CollisionBSPFunctions + ?Sized>(bsp: &BSP, reference_index: usize, mut on_surface: impl FnMut(usize)) -> Result<(), CollisionBSPError> {
+    let reference = checked_get_bsp_2d_node_reference(bsp, reference_index)?;
+    if let Some((CollisionBSP2DNodeIndexType::Surface, _)) = reference.node.as_tuple() {
+        return Err(CollisionBSPError::Bad2DReference(reference_index))
+    }
+
+    let mut visited = alloc::vec![false; bsp.get_2d_node_count()];
+    let mut stack = alloc::vec![reference.node];
+
+    while let Some(index) = stack.pop() {
+        match index.as_tuple() {
+            Some((CollisionBSP2DNodeIndexType::Node, n)) => {
+                let node = checked_get_bsp_2d_node(bsp, n)?;
+                let seen = visited.get_mut(n).ok_or(CollisionBSPError::Missing2DNode(n))?;
+                if core::mem::replace(seen, true) {
+                    return Err(CollisionBSPError::BSP2DNodeLoop(n))
+                }
+                stack.push(node.right_child);
+                stack.push(node.left_child);
+            },
+            Some((CollisionBSP2DNodeIndexType::Surface, s)) => on_surface(s),
+            None => ()
+        }
+    }
+
+    Ok(())
+}
+
+/// Get all surface indices in a leaf's 2D BSPs, deduplicated and in ascending order.
+fn leaf_surfaces<BSP: CollisionBSPFunctions + ?Sized>(bsp: &BSP, leaf_index: usize) -> Result<Vec<usize>, CollisionBSPError> {
+    let leaf = checked_get_bsp_leaf(bsp, leaf_index)?;
+    let end = leaf
+        .bsp_2d_node_reference_start
+        .checked_add(leaf.bsp_2d_node_reference_count)
+        .ok_or(CollisionBSPError::BadLeaf(leaf_index))?;
+
+    let mut surfaces = Vec::new();
+    for r in leaf.bsp_2d_node_reference_start..end {
+        walk_2d_reference(bsp, r, |s| {
+            if let Err(i) = surfaces.binary_search(&s) {
+                surfaces.insert(i, s);
+            }
+        })?;
+    }
+
+    Ok(surfaces)
+}
+
+/// Get the vertices of a surface in order.
+fn surface_vertices<BSP: CollisionBSPFunctions + ?Sized>(bsp: &BSP, surface_index: usize) -> Result<Vec<Vector3D>, CollisionBSPError> {
+    let surface = checked_get_bsp_surface(bsp, surface_index)?;
+    let mut vertices = Vec::new();
+    let mut edge_index = surface.first_edge;
+
+    loop {
+        if vertices.len() > bsp.get_edge_count() {
+            return Err(CollisionBSPError::OtherError("infinite edge loop"))
+        }
+
+        let edge = checked_get_bsp_edge(bsp, edge_index)?;
+        let reversed = edge.right_surface == surface_index;
+        let start_vertex = if reversed { edge.end_vertex } else { edge.start_vertex };
+        vertices.push(checked_get_bsp_vertex(bsp, start_vertex)?.point);
+
+        edge_index = if reversed { edge.reverse_edge } else { edge.forward_edge };
+        if edge_index == surface.first_edge {
+            return Ok(vertices)
+        }
+    }
+}
+
 fn test_vector_recursive<BSP: CollisionBSPFunctions + ?Sized>(
     bsp: &BSP,
     data: &mut CollisionBSPTestVectorState,
@@ -522,6 +755,14 @@ pub struct CollisionBSPTestVectorFlags {
     pub ignore_breakable_surfaces: bool
 }
 
+/// Options for [`CollisionBSPFunctions::nearest_surface_along`].
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct BSPQueryOptions {
+    /// Do not hit single-sided surfaces from behind (i.e. when going from solid space into empty
+    /// space).
+    pub ignore_backfaces: bool
+}
+
 /*
 boolean collision_bsp_test_vector(unsigned long flags, const struct collision_bsp *bsp, short breakable_surface_count, const byte *breakable_surface_flags, const real_point3d *point, const real_vector3d *vector, real maximum_t, struct collision_bsp_test_vector_result *result);
 static boolean collision_bsp_test_vector_recursive(struct test_vector_data *data, long child_index, real t0, real t1);
@@ -565,7 +806,10 @@ pub enum CollisionBSPError {
     MissingVertex(usize),
 
     /// An unspecified error occurred.
-    OtherError(&'static str)
+    OtherError(&'static str),
+
+    /// An infinite loop occurred in a 2D BSP. The BSP is malformed.
+    BSP2DNodeLoop(usize)
 }
 
 impl core::fmt::Display for CollisionBSPError {
@@ -583,10 +827,13 @@ impl core::fmt::Display for CollisionBSPError {
             CollisionBSPError::MissingEdge(n) => f.write_fmt(format_args!("Edge #{n} not found in the BSP; BSP is malformed")),
             CollisionBSPError::MissingVertex(n) => f.write_fmt(format_args!("Vertex #{n} not found in the BSP; BSP is malformed")),
             CollisionBSPError::OtherError(o) => f.write_str(o),
+            CollisionBSPError::BSP2DNodeLoop(n) => f.write_fmt(format_args!("An infinite BSP2D node loop occurred in the BSP including 2D node #{n}; BSP is malformed")),
         }
     }
 }
 
+impl core::error::Error for CollisionBSPError {}
+
 /// A struct containing all fields for a collision BSP 3D node.
 ///
 /// This does not correspond, bitwise, to the actual type, but it has all of its fields.
@@ -604,6 +851,9 @@ pub struct CollisionBSP3DNode {
     pub plane_index: usize
 }
 
+const _: () = assert!(size_of::<CollisionBSP3DNode>() == 0x8 + size_of::<usize>());
+const _: () = assert!(offset_of!(CollisionBSP3DNode, back_child) == 0x0 && offset_of!(CollisionBSP3DNode, front_child) == 0x4 && offset_of!(CollisionBSP3DNode, plane_index) == 0x8);
+
 /// An index for 3D nodes.
 ///
 /// The uppermost bit is used to identify if the remaining 31 bits are used for a node or leaf
@@ -697,6 +947,9 @@ pub struct CollisionBSPLeaf {
     pub bsp_2d_node_reference_count: usize
 }
 
+const _: () = assert!(size_of::<CollisionBSPLeaf>() == 3 * size_of::<usize>());
+const _: () = assert!(offset_of!(CollisionBSPLeaf, contains_double_sided_surfaces) == 0x0 && offset_of!(CollisionBSPLeaf, bsp_2d_node_reference_start) == size_of::<usize>() && offset_of!(CollisionBSPLeaf, bsp_2d_node_reference_count) == 2 * size_of::<usize>());
+
 /// A struct containing all fields for a collision BSP 2D node reference.
 ///
 /// This does not correspond, bitwise, to the actual type, but it has all of its fields.
@@ -713,6 +966,9 @@ pub struct BSP2DNodeReference {
     pub node: CollisionBSP2DNodeIndex
 }
 
+const _: () = assert!(size_of::<BSP2DNodeReference>() == 2 * size_of::<usize>());
+const _: () = assert!(offset_of!(BSP2DNodeReference, plane) == 0x0 && offset_of!(BSP2DNodeReference, node) == size_of::<usize>());
+
 /// A struct containing all fields for a collision BSP 2D node.
 ///
 /// This does not correspond, bitwise, to the actual type, but it has all of its fields.
@@ -728,6 +984,9 @@ pub struct CollisionBSP2DNode {
     pub right_child: CollisionBSP2DNodeIndex,
 }
 
+const _: () = assert!(size_of::<CollisionBSP2DNode>() == 0x14);
+const _: () = assert!(offset_of!(CollisionBSP2DNode, plane) == 0x0 && offset_of!(CollisionBSP2DNode, left_child) == 0xC && offset_of!(CollisionBSP2DNode, right_child) == 0x10);
+
 
 /// A struct containing all fields for a collision BSP surface.
 ///
@@ -783,3 +1042,508 @@ pub struct CollisionBSPVertex {
     pub point: Vector3D,
     pub first_edge: usize,
 }
+
+/// A [`CollisionBSPFunctions`] implementation for BSP data that is already stored in slices.
+#[derive(Copy, Clone, Debug, Default)]
+#[expect(missing_docs)]
+pub struct SliceBSP<'a> {
+    pub nodes_3d: &'a [CollisionBSP3DNode],
+    pub planes: &'a [Plane3D],
+    pub leaves: &'a [CollisionBSPLeaf],
+    pub node_references_2d: &'a [BSP2DNodeReference],
+    pub nodes_2d: &'a [CollisionBSP2DNode],
+    pub surfaces: &'a [CollisionBSPSurface],
+    pub edges: &'a [CollisionBSPEdge],
+    pub vertices: &'a [CollisionBSPVertex]
+}
+
+impl<'a> SliceBSP<'a> {
+    /// Instantiate a BSP from slices.
+    ///
+    /// The data is not checked. Use [`bounds_check`](CollisionBSPFunctions::bounds_check) to do so.
+    #[must_use]
+    #[expect(clippy::too_many_arguments)]
+    pub const fn new(
+        nodes_3d: &'a [CollisionBSP3DNode],
+        planes: &'a [Plane3D],
+        leaves: &'a [CollisionBSPLeaf],
+        node_references_2d: &'a [BSP2DNodeReference],
+        nodes_2d: &'a [CollisionBSP2DNode],
+        surfaces: &'a [CollisionBSPSurface],
+        edges: &'a [CollisionBSPEdge],
+        vertices: &'a [CollisionBSPVertex]
+    ) -> Self {
+        Self { nodes_3d, planes, leaves, node_references_2d, nodes_2d, surfaces, edges, vertices }
+    }
+}
+
+impl CollisionBSPFunctions for SliceBSP<'_> {
+    fn get_3d_node(&self, node: usize) -> Option<CollisionBSP3DNode> { self.nodes_3d.get(node).copied() }
+    fn get_3d_node_count(&self) -> usize { self.nodes_3d.len() }
+    fn get_plane(&self, plane: usize) -> Option<Plane3D> { self.planes.get(plane).copied() }
+    fn get_plane_count(&self) -> usize { self.planes.len() }
+    fn get_leaf(&self, leaf: usize) -> Option<CollisionBSPLeaf> { self.leaves.get(leaf).copied() }
+    fn get_leaf_count(&self) -> usize { self.leaves.len() }
+    fn get_2d_node_reference(&self, node: usize) -> Option<BSP2DNodeReference> { self.node_references_2d.get(node).copied() }
+    fn get_2d_node_reference_count(&self) -> usize { self.node_references_2d.len() }
+    fn get_2d_node(&self, node: usize) -> Option<CollisionBSP2DNode> { self.nodes_2d.get(node).copied() }
+    fn get_2d_node_count(&self) -> usize { self.nodes_2d.len() }
+    fn get_surface(&self, surface: usize) -> Option<CollisionBSPSurface> { self.surfaces.get(surface).copied() }
+    fn get_surface_count(&self) -> usize { self.surfaces.len() }
+    fn get_edge(&self, edge: usize) -> Option<CollisionBSPEdge> { self.edges.get(edge).copied() }
+    fn get_edge_count(&self) -> usize { self.edges.len() }
+    fn get_vertex(&self, vertex: usize) -> Option<CollisionBSPVertex> { self.vertices.get(vertex).copied() }
+    fn get_vertex_count(&self) -> usize { self.vertices.len() }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+    use super::*;
+
+    fn surface_on_plane(plane: usize) -> CollisionBSPSurface {
+        CollisionBSPSurface {
+            plane,
+            first_edge: 0,
+            flags: CollisionBSPSurfaceFlags { two_sided: false, invisible: false, climbable: false, breakable: false },
+            material: 0,
+            breakable_surface_index: None
+        }
+    }
+
+    const fn surface_index(surface: u32) -> CollisionBSP2DNodeIndex {
+        CollisionBSP2DNodeIndex(0x80000000 | surface)
+    }
+
+    /// Two leaves: leaf 0 has surfaces 0 and 1 on plane 0 and surface 2 on plane 2, and leaf 1 has
+    /// surface 3 on plane 1.
+    ///
+    /// Surface 0 is a quad from (0,0,0) to (2,3,0). Surfaces 1 and 2 are triangles sharing an edge
+    /// with it, reaching (-1,1,0) and (2,0,5), respectively. Surface 3 is a triangle within
+    /// (10,10,9) to (11,12,10).
+    fn two_leaf_bsp() -> SliceBSP<'static> {
+        let plane = |x, y, z, offset| Plane3D { vector: Vector3D { x, y, z }, offset };
+        let node_2d = |left_child, right_child| CollisionBSP2DNode {
+            plane: Plane2D { offset: 0.0, vector: Vector2D { x: 1.0, y: 0.0 } },
+            left_child,
+            right_child
+        };
+        let surface = |plane, first_edge| CollisionBSPSurface { first_edge, ..surface_on_plane(plane) };
+        let vertex = |x, y, z| CollisionBSPVertex { point: Vector3D { x, y, z }, first_edge: 0 };
+        let edge = |start_vertex, end_vertex, forward_edge, reverse_edge, left_surface, right_surface| CollisionBSPEdge {
+            start_vertex, end_vertex, forward_edge, reverse_edge, left_surface, right_surface
+        };
+        let none = usize::MAX;
+
+        SliceBSP {
+            planes: vec![plane(0.0, 0.0, 1.0, 0.0), plane(0.0, 0.0, -1.0, -1.0), plane(1.0, 0.0, 0.0, 1.0)].leak(),
+            leaves: vec![
+                CollisionBSPLeaf { contains_double_sided_surfaces: false, bsp_2d_node_reference_start: 0, bsp_2d_node_reference_count: 2 },
+                CollisionBSPLeaf { contains_double_sided_surfaces: false, bsp_2d_node_reference_start: 2, bsp_2d_node_reference_count: 1 },
+            ].leak(),
+            node_references_2d: vec![
+                BSP2DNodeReference { plane: 0, node: CollisionBSP2DNodeIndex(0) },
+                BSP2DNodeReference { plane: 2, node: CollisionBSP2DNodeIndex(1) },
+                BSP2DNodeReference { plane: 1, node: CollisionBSP2DNodeIndex(2) },
+            ].leak(),
+            nodes_2d: vec![
+                node_2d(surface_index(1), surface_index(0)),
+                node_2d(CollisionBSP2DNodeIndex(0xFFFFFFFF), surface_index(2)),
+                node_2d(surface_index(3), CollisionBSP2DNodeIndex(0xFFFFFFFF)),
+            ].leak(),
+            surfaces: vec![surface(0, 0), surface(0, 6), surface(2, 4), surface(1, 8)].leak(),
+            edges: vec![
+                edge(0, 1, 1, 0, 0, none),
+                edge(1, 2, 2, 4, 0, 2),
+                edge(2, 3, 3, 2, 0, none),
+                edge(3, 0, 0, 6, 0, 1),
+                edge(1, 4, 5, 4, 2, none),
+                edge(4, 2, 1, 5, 2, none),
+                edge(3, 5, 7, 6, 1, none),
+                edge(5, 0, 3, 7, 1, none),
+                edge(6, 7, 9, 8, 3, none),
+                edge(7, 8, 10, 9, 3, none),
+                edge(8, 6, 8, 10, 3, none),
+            ].leak(),
+            vertices: vec![
+                vertex(0.0, 0.0, 0.0),
+                vertex(2.0, 0.0, 0.0),
+                vertex(2.0, 3.0, 0.0),
+                vertex(0.0, 3.0, 0.0),
+                vertex(2.0, 0.0, 5.0),
+                vertex(-1.0, 1.0, 0.0),
+                vertex(10.0, 10.0, 10.0),
+                vertex(11.0, 10.0, 10.0),
+                vertex(10.0, 12.0, 9.0),
+            ].leak(),
+            ..Default::default()
+        }
+    }
+
+    /// One empty leaf above a floor surface on z = 0, with solid space below it.
+    fn floor_bsp() -> SliceBSP<'static> {
+        SliceBSP {
+            nodes_3d: vec![CollisionBSP3DNode {
+                back_child: CollisionBSP3DNodeIndex(0xFFFFFFFF),
+                front_child: CollisionBSP3DNodeIndex(0x80000000),
+                plane_index: 0
+            }].leak(),
+            planes: vec![Plane3D { vector: Vector3D { x: 0.0, y: 0.0, z: 1.0 }, offset: 0.0 }].leak(),
+            leaves: vec![CollisionBSPLeaf { contains_double_sided_surfaces: false, bsp_2d_node_reference_start: 0, bsp_2d_node_reference_count: 1 }].leak(),
+            node_references_2d: vec![BSP2DNodeReference { plane: 0, node: CollisionBSP2DNodeIndex(0) }].leak(),
+            nodes_2d: vec![CollisionBSP2DNode {
+                plane: Plane2D { offset: 0.0, vector: Vector2D { x: 1.0, y: 0.0 } },
+                left_child: surface_index(0),
+                right_child: surface_index(0)
+            }].leak(),
+            surfaces: vec![surface_on_plane(0)].leak(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn nearest_surface_along() {
+        let bsp = floor_bsp();
+        let down = Vector3D { x: 0.0, y: 0.0, z: -10.0 };
+        let options = BSPQueryOptions::default();
+
+        assert_eq!(bsp.nearest_surface_along(Vector3D { x: 1.0, y: 2.0, z: 5.0 }, down, options).unwrap(), Some((0, 5.0)));
+        assert_eq!(bsp.nearest_surface_along(Vector3D { x: 1.0, y: 2.0, z: 2.5 }, down.scaled(0.1), options).unwrap(), None, "too short");
+        assert_eq!(bsp.nearest_surface_along(Vector3D { x: 1.0, y: 2.0, z: 5.0 }, down.scaled(-1.0), options).unwrap(), None, "going up");
+
+        // starting outside the BSP (below the floor)
+        assert_eq!(bsp.nearest_surface_along(Vector3D { x: 1.0, y: 2.0, z: -5.0 }, down, options).unwrap(), None);
+    }
+
+    #[test]
+    fn nearest_surface_along_backfaces() {
+        let bsp = floor_bsp();
+        let above = Vector3D { x: 1.0, y: 2.0, z: 5.0 };
+        let below = Vector3D { x: 1.0, y: 2.0, z: -5.0 };
+        let down = Vector3D { x: 0.0, y: 0.0, z: -10.0 };
+        let up = down.scaled(-1.0);
+
+        let include = BSPQueryOptions { ignore_backfaces: false };
+        let ignore = BSPQueryOptions { ignore_backfaces: true };
+
+        assert_eq!(bsp.nearest_surface_along(above, down, include).unwrap(), Some((0, 5.0)));
+        assert_eq!(bsp.nearest_surface_along(above, down, ignore).unwrap(), Some((0, 5.0)));
+        assert_eq!(bsp.nearest_surface_along(below, up, include).unwrap(), Some((0, 5.0)));
+        assert_eq!(bsp.nearest_surface_along(below, up, ignore).unwrap(), None);
+
+        // A two-sided surface between two leaves with double-sided surfaces is hit from both sides
+        let mut nodes_3d = bsp.nodes_3d.to_vec();
+        nodes_3d[0].back_child = CollisionBSP3DNodeIndex(0x80000001);
+        let mut leaves = bsp.leaves.to_vec();
+        leaves[0].contains_double_sided_surfaces = true;
+        leaves.push(leaves[0]);
+        let mut surfaces = bsp.surfaces.to_vec();
+        surfaces[0].flags.two_sided = true;
+
+        let vertex = |x, y| CollisionBSPVertex { point: Vector3D { x, y, z: 0.0 }, first_edge: 0 };
+        let vertices = [vertex(-10.0, -10.0), vertex(10.0, -10.0), vertex(10.0, 10.0), vertex(-10.0, 10.0)];
+        let edges: Vec<_> = (0..4).map(|e| CollisionBSPEdge {
+            start_vertex: e,
+            end_vertex: (e + 1) % 4,
+            forward_edge: (e + 1) % 4,
+            reverse_edge: e,
+            left_surface: 0,
+            right_surface: usize::MAX
+        }).collect();
+        let two_sided = SliceBSP { nodes_3d: &nodes_3d, leaves: &leaves, surfaces: &surfaces, edges: &edges, vertices: &vertices, ..bsp };
+
+        for options in [include, ignore] {
+            assert_eq!(two_sided.nearest_surface_along(above, down, options).unwrap(), Some((0, 5.0)));
+            assert_eq!(two_sided.nearest_surface_along(below, up, options).unwrap(), Some((0, 5.0)));
+            assert_eq!(two_sided.nearest_surface_along(Vector3D { x: 20.0, y: 2.0, z: 5.0 }, down, options).unwrap(), None, "misses the surface");
+        }
+    }
+
+    #[test]
+    fn leaf_indices_for_points() {
+        let floor = floor_bsp();
+
+        // split the empty space at x = 0 into leaf 0 (x >= 0) and leaf 1 (x < 0)
+        let mut nodes_3d = floor.nodes_3d.to_vec();
+        nodes_3d[0].front_child = CollisionBSP3DNodeIndex(1);
+        nodes_3d.push(CollisionBSP3DNode {
+            back_child: CollisionBSP3DNodeIndex(0x80000001),
+            front_child: CollisionBSP3DNodeIndex(0x80000000),
+            plane_index: 1
+        });
+        let planes = [floor.planes[0], Plane3D { vector: Vector3D { x: 1.0, y: 0.0, z: 0.0 }, offset: 0.0 }];
+        let leaves = [floor.leaves[0]; 2];
+        let bsp = SliceBSP { nodes_3d: &nodes_3d, planes: &planes, leaves: &leaves, ..floor };
+
+        let points = [
+            Vector3D { x: 1.0, y: 0.0, z: 1.0 },
+            Vector3D { x: -1.0, y: 5.0, z: 1.0 },
+            Vector3D { x: 1.0, y: 0.0, z: -1.0 },
+            Vector3D { x: 0.0, y: 0.0, z: 0.0 },
+            Vector3D { x: -3.0, y: -3.0, z: -3.0 },
+        ];
+        let mut out = [Some(usize::MAX); 5];
+        bsp.leaf_indices_for_points(&points, &mut out).unwrap();
+        assert_eq!(out, [Some(0), Some(1), None, Some(0), None]);
+
+        for (point, leaf) in points.iter().zip(out) {
+            assert_eq!(bsp.leaf_index_for_point_3d(*point).unwrap(), leaf);
+        }
+
+        bsp.leaf_indices_for_points(&[], &mut []).unwrap();
+
+        let mut looped = nodes_3d.clone();
+        looped[1].front_child = CollisionBSP3DNodeIndex(1);
+        let looped = SliceBSP { nodes_3d: &looped, ..bsp };
+        assert!(matches!(looped.leaf_indices_for_points(&points, &mut out), Err(CollisionBSPError::BSP3DNodeLoop(1))));
+        assert!(matches!(looped.leaf_index_for_point_3d(points[0]), Err(CollisionBSPError::BSP3DNodeLoop(1))));
+    }
+
+    #[test]
+    fn adjacent_leaves() {
+        // leaf 0 (x >= 0) and leaf 1 (x < 0) separated by a two-sided surface on x = 0
+        let nodes_3d = [CollisionBSP3DNode {
+            back_child: CollisionBSP3DNodeIndex(0x80000001),
+            front_child: CollisionBSP3DNodeIndex(0x80000000),
+            plane_index: 0
+        }];
+        let planes = [Plane3D { vector: Vector3D { x: 1.0, y: 0.0, z: 0.0 }, offset: 0.0 }];
+        let leaves = [CollisionBSPLeaf { contains_double_sided_surfaces: true, bsp_2d_node_reference_start: 0, bsp_2d_node_reference_count: 1 }; 2];
+        let node_references_2d = [BSP2DNodeReference { plane: 0, node: CollisionBSP2DNodeIndex(0) }];
+        let nodes_2d = [CollisionBSP2DNode {
+            plane: Plane2D { offset: 0.0, vector: Vector2D { x: 1.0, y: 0.0 } },
+            left_child: surface_index(0),
+            right_child: surface_index(0)
+        }];
+        let surfaces = [surface_on_plane(0)];
+        let edges: Vec<_> = (0..4).map(|e| CollisionBSPEdge {
+            start_vertex: e,
+            end_vertex: (e + 1) % 4,
+            forward_edge: (e + 1) % 4,
+            reverse_edge: e,
+            left_surface: 0,
+            right_surface: 0
+        }).collect();
+        let vertex = |y, z| CollisionBSPVertex { point: Vector3D { x: 0.0, y, z }, first_edge: 0 };
+        let vertices = [vertex(-1.0, -1.0), vertex(1.0, -1.0), vertex(1.0, 1.0), vertex(-1.0, 1.0)];
+
+        let bsp = SliceBSP::new(&nodes_3d, &planes, &leaves, &node_references_2d, &nodes_2d, &surfaces, &edges, &vertices);
+        bsp.bounds_check().unwrap();
+
+        assert_eq!(bsp.adjacent_leaves(0).unwrap().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(bsp.adjacent_leaves(1).unwrap().collect::<Vec<_>>(), vec![0]);
+        assert!(matches!(bsp.adjacent_leaves(2), Err(CollisionBSPError::MissingLeaf(2))));
+
+        // solid space is not a leaf
+        let vertex = |x, y| CollisionBSPVertex { point: Vector3D { x, y, z: 0.0 }, first_edge: 0 };
+        let vertices = [vertex(0.0, 0.0), vertex(1.0, 0.0), vertex(0.0, 1.0)];
+        let edges: Vec<_> = (0..3).map(|e| CollisionBSPEdge {
+            start_vertex: e,
+            end_vertex: (e + 1) % 3,
+            forward_edge: (e + 1) % 3,
+            reverse_edge: e,
+            left_surface: 0,
+            right_surface: usize::MAX
+        }).collect();
+        let floor = SliceBSP { edges: &edges, vertices: &vertices, ..floor_bsp() };
+        assert_eq!(floor.adjacent_leaves(0).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn slice_bsp() {
+        let nodes_3d = [CollisionBSP3DNode {
+            back_child: CollisionBSP3DNodeIndex(0xFFFFFFFF),
+            front_child: CollisionBSP3DNodeIndex(0x80000000),
+            plane_index: 0
+        }];
+        let planes = [Plane3D { vector: Vector3D { x: 0.0, y: 0.0, z: 1.0 }, offset: 0.0 }];
+        let leaves = [CollisionBSPLeaf { contains_double_sided_surfaces: false, bsp_2d_node_reference_start: 0, bsp_2d_node_reference_count: 1 }];
+        let node_references_2d = [BSP2DNodeReference { plane: 0, node: CollisionBSP2DNodeIndex(0) }];
+        let nodes_2d = [CollisionBSP2DNode {
+            plane: Plane2D { offset: 0.0, vector: Vector2D { x: 1.0, y: 0.0 } },
+            left_child: surface_index(0),
+            right_child: surface_index(0)
+        }];
+        let surfaces = [surface_on_plane(0)];
+        let edges = [CollisionBSPEdge { start_vertex: 0, end_vertex: 0, forward_edge: 0, reverse_edge: 0, left_surface: 0, right_surface: 0 }];
+        let vertices = [CollisionBSPVertex { point: Vector3D::ZEROED, first_edge: 0 }];
+
+        let bsp = SliceBSP::new(&nodes_3d, &planes, &leaves, &node_references_2d, &nodes_2d, &surfaces, &edges, &vertices);
+        assert_eq!(bsp.get_leaf_count(), 1);
+        assert!(bsp.point_inside_bsp(Vector3D { x: 5.0, y: -3.0, z: 1.0 }).unwrap());
+        assert!(!bsp.point_inside_bsp(Vector3D { x: 5.0, y: -3.0, z: -1.0 }).unwrap());
+        assert_eq!(bsp.surfaces_in_leaf(0).unwrap().collect::<Vec<_>>(), vec![0]);
+
+        bsp.bounds_check().unwrap();
+
+        let bad_plane = SliceBSP { planes: &[], ..bsp };
+        assert!(matches!(bad_plane.bounds_check(), Err(CollisionBSPError::MissingPlane(0))));
+        assert!(matches!(bad_plane.point_inside_bsp(Vector3D::ZEROED), Err(CollisionBSPError::MissingPlane(0))));
+
+        let bad_leaf = SliceBSP { leaves: &[], ..bsp };
+        assert!(matches!(bad_leaf.bounds_check(), Err(CollisionBSPError::MissingLeaf(0))));
+    }
+
+    #[test]
+    fn bounds_check() {
+        // leaf 0 (x >= 0) and leaf 1 (x < 0) separated by a surface on x = 0
+        let nodes_3d = [CollisionBSP3DNode {
+            back_child: CollisionBSP3DNodeIndex(0x80000001),
+            front_child: CollisionBSP3DNodeIndex(0x80000000),
+            plane_index: 0
+        }];
+        let planes = [Plane3D { vector: Vector3D { x: 1.0, y: 0.0, z: 0.0 }, offset: 0.0 }];
+        let leaves = [
+            CollisionBSPLeaf { contains_double_sided_surfaces: false, bsp_2d_node_reference_start: 0, bsp_2d_node_reference_count: 1 },
+            CollisionBSPLeaf { contains_double_sided_surfaces: false, bsp_2d_node_reference_start: 1, bsp_2d_node_reference_count: 1 },
+        ];
+        let node_references_2d = [
+            BSP2DNodeReference { plane: 0, node: CollisionBSP2DNodeIndex(0) },
+            BSP2DNodeReference { plane: 0, node: CollisionBSP2DNodeIndex(0xFFFFFFFF) },
+        ];
+        let nodes_2d = [CollisionBSP2DNode {
+            plane: Plane2D { offset: 0.0, vector: Vector2D { x: 1.0, y: 0.0 } },
+            left_child: surface_index(0),
+            right_child: CollisionBSP2DNodeIndex(0xFFFFFFFF)
+        }];
+        let surfaces = [surface_on_plane(0)];
+        let edges = [CollisionBSPEdge { start_vertex: 0, end_vertex: 0, forward_edge: 0, reverse_edge: 0, left_surface: 0, right_surface: 0 }];
+        let vertices = [CollisionBSPVertex { point: Vector3D::ZEROED, first_edge: 0 }];
+
+        let bsp = SliceBSP::new(&nodes_3d, &planes, &leaves, &node_references_2d, &nodes_2d, &surfaces, &edges, &vertices);
+        assert_eq!(bsp.get_leaf_count(), 2);
+        bsp.bounds_check().unwrap();
+
+        let missing_leaf = SliceBSP { leaves: &leaves[..1], ..bsp };
+        assert!(matches!(missing_leaf.bounds_check(), Err(CollisionBSPError::MissingLeaf(1))));
+
+        let bad_reference = [leaves[0], CollisionBSPLeaf { bsp_2d_node_reference_count: 2, ..leaves[1] }];
+        let bad_reference = SliceBSP { leaves: &bad_reference, ..bsp };
+        assert!(matches!(bad_reference.bounds_check(), Err(CollisionBSPError::Missing2DNodeReference(2))));
+
+        // 2D references must point to a 2D node, not directly to a surface
+        let direct_surface = [node_references_2d[0], BSP2DNodeReference { node: surface_index(0), ..node_references_2d[1] }];
+        let direct_surface = SliceBSP { node_references_2d: &direct_surface, ..bsp };
+        assert!(matches!(direct_surface.bounds_check(), Err(CollisionBSPError::Bad2DReference(1))));
+        assert!(matches!(direct_surface.surfaces_in_leaf(1), Err(CollisionBSPError::Bad2DReference(1))));
+
+        // a 2D node that points back to itself
+        let looped = [CollisionBSP2DNode { right_child: CollisionBSP2DNodeIndex(0), ..nodes_2d[0] }];
+        let looped = SliceBSP { nodes_2d: &looped, ..bsp };
+        assert!(matches!(looped.bounds_check(), Err(CollisionBSPError::BSP2DNodeLoop(0))));
+
+        // two nodes that point to each other
+        let looped = [
+            CollisionBSP2DNode { right_child: CollisionBSP2DNodeIndex(1), ..nodes_2d[0] },
+            CollisionBSP2DNode { left_child: CollisionBSP2DNodeIndex(0), ..nodes_2d[0] },
+        ];
+        let looped = SliceBSP { nodes_2d: &looped, ..bsp };
+        assert!(matches!(looped.bounds_check(), Err(CollisionBSPError::BSP2DNodeLoop(_))));
+
+        // the same node reached twice is also not a tree
+        let shared = [
+            CollisionBSP2DNode { left_child: CollisionBSP2DNodeIndex(1), right_child: CollisionBSP2DNodeIndex(1), ..nodes_2d[0] },
+            nodes_2d[0],
+        ];
+        let shared = SliceBSP { nodes_2d: &shared, ..bsp };
+        assert!(matches!(shared.bounds_check(), Err(CollisionBSPError::BSP2DNodeLoop(1))));
+
+        // even when there are enough unused nodes that the walk never visits more nodes than exist
+        let shared_with_spare = [
+            CollisionBSP2DNode { left_child: CollisionBSP2DNodeIndex(1), right_child: CollisionBSP2DNodeIndex(1), ..nodes_2d[0] },
+            nodes_2d[0],
+            nodes_2d[0],
+            nodes_2d[0],
+        ];
+        let shared_with_spare = SliceBSP { nodes_2d: &shared_with_spare, ..bsp };
+        assert!(matches!(shared_with_spare.bounds_check(), Err(CollisionBSPError::BSP2DNodeLoop(1))));
+        assert!(matches!(shared_with_spare.surfaces_in_leaf(0), Err(CollisionBSPError::BSP2DNodeLoop(1))));
+    }
+
+    #[test]
+    fn error_trait() {
+        use alloc::string::ToString;
+
+        let error = CollisionBSPError::MissingPlane(3);
+        let error: &dyn core::error::Error = &error;
+        assert_eq!(error.to_string(), "Plane #3 not found in the BSP; BSP is malformed");
+        assert!(error.source().is_none());
+
+        let boxed: alloc::boxed::Box<dyn core::error::Error> = CollisionBSPError::OtherError("oops").into();
+        assert_eq!(boxed.to_string(), "oops");
+    }
+
+    #[test]
+    fn surfaces_in_leaf() {
+        let bsp = two_leaf_bsp();
+        assert_eq!(bsp.surfaces_in_leaf(0).unwrap().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(bsp.surfaces_in_leaf(1).unwrap().collect::<Vec<_>>(), vec![3]);
+        assert!(matches!(bsp.surfaces_in_leaf(2), Err(CollisionBSPError::MissingLeaf(2))));
+
+        let mut leaves = bsp.leaves.to_vec();
+        leaves[1].bsp_2d_node_reference_count = 5;
+        let bad_reference = SliceBSP { leaves: &leaves, ..bsp };
+        assert!(matches!(bad_reference.surfaces_in_leaf(1), Err(CollisionBSPError::Missing2DNodeReference(3))));
+
+        let mut nodes_2d = bsp.nodes_2d.to_vec();
+        nodes_2d[2].left_child = CollisionBSP2DNodeIndex(7);
+        let bad_node = SliceBSP { nodes_2d: &nodes_2d, ..bsp };
+        assert!(matches!(bad_node.surfaces_in_leaf(1), Err(CollisionBSPError::Missing2DNode(7))));
+
+        // references must point to a 2D node, not directly to a surface
+        let mut node_references_2d = bsp.node_references_2d.to_vec();
+        node_references_2d[2].node = surface_index(3);
+        let direct_surface = SliceBSP { node_references_2d: &node_references_2d, ..bsp };
+        assert!(matches!(direct_surface.surfaces_in_leaf(1), Err(CollisionBSPError::Bad2DReference(2))));
+        assert!(matches!(direct_surface.leaf_planes(1), Err(CollisionBSPError::Bad2DReference(2))));
+        assert!(matches!(direct_surface.leaf_bounding_box(1), Err(CollisionBSPError::Bad2DReference(2))));
+        assert!(direct_surface.surfaces_in_leaf(0).is_ok());
+    }
+
+    #[test]
+    fn leaf_bounding_box() {
+        let bsp = two_leaf_bsp();
+
+        assert_eq!(surface_vertices(&bsp, 0).unwrap(), vec![bsp.vertices[0].point, bsp.vertices[1].point, bsp.vertices[2].point, bsp.vertices[3].point]);
+        assert_eq!(surface_vertices(&bsp, 1).unwrap(), vec![bsp.vertices[3].point, bsp.vertices[5].point, bsp.vertices[0].point]);
+        assert_eq!(surface_vertices(&bsp, 2).unwrap(), vec![bsp.vertices[1].point, bsp.vertices[4].point, bsp.vertices[2].point]);
+
+        assert_eq!(bsp.leaf_bounding_box(0).unwrap(), Some(Cube3D { top: 5.0, left: 3.0, bottom: 0.0, right: 0.0, front: 2.0, back: -1.0 }));
+        assert_eq!(bsp.leaf_bounding_box(1).unwrap(), Some(Cube3D { top: 10.0, left: 12.0, bottom: 9.0, right: 10.0, front: 11.0, back: 10.0 }));
+
+        let mut leaves = bsp.leaves.to_vec();
+        leaves[1].bsp_2d_node_reference_count = 0;
+        let empty = SliceBSP { leaves: &leaves, ..bsp };
+        assert_eq!(empty.leaf_bounding_box(1).unwrap(), None);
+    }
+
+    #[test]
+    fn leaf_planes() {
+        let bsp = two_leaf_bsp();
+        assert_eq!(bsp.leaf_planes(0).unwrap().collect::<Vec<_>>(), vec![(0, bsp.planes[0]), (2, bsp.planes[2])]);
+        assert_eq!(bsp.leaf_planes(1).unwrap().collect::<Vec<_>>(), vec![(1, bsp.planes[1])]);
+        assert!(matches!(bsp.leaf_planes(2), Err(CollisionBSPError::MissingLeaf(2))));
+
+        let mut nodes_2d = bsp.nodes_2d.to_vec();
+        nodes_2d[1].left_child = CollisionBSP2DNodeIndex(1);
+        let looped = SliceBSP { nodes_2d: &nodes_2d, ..bsp };
+        assert!(matches!(looped.leaf_planes(0), Err(CollisionBSPError::BSP2DNodeLoop(1))));
+    }
+
+    #[test]
+    fn coplanar_surface_groups() {
+        let floor = Plane3D { vector: Vector3D { x: 0.0, y: 0.0, z: 1.0 }, offset: 0.0 };
+        let wall = Plane3D { vector: Vector3D { x: 1.0, y: 0.0, z: 0.0 }, offset: 1.0 };
+        let ceiling = Plane3D { vector: Vector3D { x: 0.0, y: 0.0, z: -1.0 }, offset: 0.0 };
+
+        // plane 2 is a duplicate of plane 0, and plane 3 is plane 0 facing the other way
+        let planes = [floor, wall, floor, ceiling];
+        let surfaces = [surface_on_plane(0), surface_on_plane(1), surface_on_plane(2), surface_on_plane(0), surface_on_plane(3)];
+        let bsp = SliceBSP { planes: &planes, surfaces: &surfaces, ..Default::default() };
+
+        assert_eq!(bsp.coplanar_surface_groups().unwrap(), vec![vec![0, 2, 3], vec![1], vec![4]]);
+
+        let bad = SliceBSP { surfaces: &surfaces[..1], ..Default::default() };
+        assert!(matches!(bad.coplanar_surface_groups(), Err(CollisionBSPError::MissingPlane(0))));
+    }
+}
@@ -1,5 +1,10 @@
 //! Provides operations for colors.
 
+use core::mem::offset_of;
+use core::ops::{Add, Mul};
+use crate::float::FloatOps;
+use crate::vector::Vector4D;
+
 /// [`ColorRGB`] with alpha (transparency) component
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
@@ -11,6 +16,10 @@ pub struct ColorARGB {
     /// Color values
     pub color: ColorRGB
 }
+
+const _: () = assert!(size_of::<ColorARGB>() == 0x10);
+const _: () = assert!(offset_of!(ColorARGB, a) == 0x0 && offset_of!(ColorARGB, color) == 0x4);
+
 impl AsRef<ColorARGB> for ColorARGB {
     fn as_ref(&self) -> &ColorARGB {
         self
@@ -26,6 +35,24 @@ impl ColorARGB {
         }
     }
 
+    /// Instantiate a color from 8-bit channels, where 0 is 0.0 and 255 is 1.0.
+    #[must_use]
+    pub const fn from_argb8(a: u8, r: u8, g: u8, b: u8) -> Self {
+        ColorARGB {
+            a: a as f32 / 255.0,
+            color: ColorRGB::from_rgb8(r, g, b)
+        }
+    }
+
+    /// Instantiate a color from a `0xAARRGGBB` value.
+    ///
+    /// This is the same layout as [`Pixel32`].
+    #[must_use]
+    pub const fn from_hex(hex: u32) -> Self {
+        let [a, r, g, b] = hex.to_be_bytes();
+        Self::from_argb8(a, r, g, b)
+    }
+
     /// Returns `true` the color is valid (i.e. all channels are between 0 and 1, inclusive).
     #[must_use] 
     pub const fn is_valid(&self) -> bool {
@@ -36,11 +63,30 @@ impl ColorARGB {
     ///
     /// # Panics
     ///
-    /// Panics if `!self.is_valid()`.
+    /// Panics if `!self.is_valid()`. Use [`try_to_pixel32`](Self::try_to_pixel32) to handle this.
     #[must_use] 
     pub const fn to_pixel32(&self) -> Pixel32 {
-        assert!(self.is_valid());
+        self.try_to_pixel32().expect("color is not valid")
+    }
 
+    /// Convert the value to a [`Pixel32`], or `None` if `!self.is_valid()`.
+    #[must_use]
+    pub const fn try_to_pixel32(&self) -> Option<Pixel32> {
+        if !self.is_valid() {
+            return None
+        }
+        Some(self.pack_pixel32())
+    }
+
+    /// Convert the value to a [`Pixel32`], clamping each channel to `[0..1]` first.
+    ///
+    /// NaN channels are packed as 0.
+    #[must_use]
+    pub const fn to_pixel32_clamped(&self) -> Pixel32 {
+        self.clamped().pack_pixel32()
+    }
+
+    const fn pack_pixel32(&self) -> Pixel32 {
         let a = (self.a * 255.0) as u32;
         let r = (self.color.r * 255.0) as u32;
         let g = (self.color.g * 255.0) as u32;
@@ -48,6 +94,59 @@ impl ColorARGB {
         Pixel32((a << 24) | (r << 16) | (g << 8) | b)
     }
 
+    /// Convert the color to a [`Vector4D`].
+    ///
+    /// The channels are mapped in RGBA order like in a shader, so red is `x`, green is `y`, blue
+    /// is `z`, and alpha is `w`.
+    #[must_use]
+    pub const fn as_vector4d(self) -> Vector4D {
+        Vector4D { x: self.color.r, y: self.color.g, z: self.color.b, w: self.a }
+    }
+
+    /// Convert a [`Vector4D`] to a color.
+    ///
+    /// This is the reverse of [`as_vector4d`](Self::as_vector4d), so `x` is red, `y` is green, `z`
+    /// is blue, and `w` is alpha. No clamping is done.
+    #[must_use]
+    pub const fn from_vector4d(vector: Vector4D) -> Self {
+        ColorARGB { a: vector.w, color: ColorRGB { r: vector.x, g: vector.y, b: vector.z } }
+    }
+
+    /// Interpolate this color with another one by `by` amount.
+    ///
+    /// `by` is clamped to `[0..1]`. Each channel, including alpha, is interpolated as-is (i.e. in
+    /// gamma space), matching how the game blends colors.
+    #[must_use]
+    pub const fn linear_interpolated(self, with: ColorARGB, by: f32) -> ColorARGB {
+        let by = by.clamp(0.0, 1.0);
+        ColorARGB {
+            a: self.a + (with.a - self.a) * by,
+            color: self.color.linear_interpolated(with.color, by)
+        }
+    }
+
+    /// Composite this color over `background` using straight (non-premultiplied) alpha.
+    ///
+    /// Like [`linear_interpolated`](Self::linear_interpolated), this is done in gamma space.
+    /// Compositing two fully transparent colors results in transparent black.
+    #[must_use]
+    pub const fn over(self, background: ColorARGB) -> ColorARGB {
+        let background_weight = background.a * (1.0 - self.a);
+        let a = self.a + background_weight;
+        if a <= 0.0 {
+            return ColorARGB::new()
+        }
+
+        ColorARGB {
+            a,
+            color: ColorRGB {
+                r: (self.color.r * self.a + background.color.r * background_weight) / a,
+                g: (self.color.g * self.a + background.color.g * background_weight) / a,
+                b: (self.color.b * self.a + background.color.b * background_weight) / a
+            }
+        }
+    }
+
     /// Clamps all values to between 0 and 1 (inclusive).
     #[must_use] 
     pub const fn clamped(self) -> ColorARGB {
@@ -69,6 +168,9 @@ impl ColorARGB {
 /// as this would make the value greater than 1.
 ///
 /// This essentially puts it as slightly less than a 30-bit value.
+///
+/// The arithmetic operators (`+` and `*`) work on each channel and do not clamp, so the result may
+/// go outside of `[0..1]` (e.g. when adding lights). Use [`ColorRGB::clamped`] if needed.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
 #[repr(C)]
@@ -83,6 +185,9 @@ pub struct ColorRGB {
     pub b: f32
 }
 
+const _: () = assert!(size_of::<ColorRGB>() == 0xC);
+const _: () = assert!(offset_of!(ColorRGB, r) == 0x0 && offset_of!(ColorRGB, g) == 0x4 && offset_of!(ColorRGB, b) == 0x8);
+
 impl ColorRGB {
     /// The color white (rgb = 1.0).
     pub const WHITE: ColorRGB = ColorRGB { r: 1.0, g: 1.0, b: 1.0 };
@@ -96,6 +201,33 @@ impl ColorRGB {
         Self::BLACK
     }
 
+    /// Instantiate a color from its channels.
+    ///
+    /// The channels are not checked. Use [`try_from_rgb`](Self::try_from_rgb) to validate them.
+    #[must_use]
+    pub const fn from_rgb(r: f32, g: f32, b: f32) -> Self {
+        ColorRGB { r, g, b }
+    }
+
+    /// Instantiate a color from its channels, or `None` if the result is not
+    /// [valid](Self::is_valid).
+    #[must_use]
+    pub const fn try_from_rgb(r: f32, g: f32, b: f32) -> Option<Self> {
+        let color = Self::from_rgb(r, g, b);
+        if color.is_valid() {
+            Some(color)
+        }
+        else {
+            None
+        }
+    }
+
+    /// Instantiate a color from 8-bit channels, where 0 is 0.0 and 255 is 1.0.
+    #[must_use]
+    pub const fn from_rgb8(r: u8, g: u8, b: u8) -> Self {
+        ColorRGB { r: r as f32 / 255.0, g: g as f32 / 255.0, b: b as f32 / 255.0 }
+    }
+
     /// Returns `true` if the color is valid.
     #[must_use] 
     pub const fn is_valid(&self) -> bool {
@@ -115,6 +247,222 @@ impl ColorRGB {
     pub const fn clamped(self) -> ColorRGB {
         ColorRGB { r: self.r.clamp(0.0, 1.0), g: self.g.clamp(0.0, 1.0), b: self.b.clamp(0.0, 1.0) }
     }
+
+    /// Multiply all channels with `by`.
+    ///
+    /// The result is not clamped, so it may go outside of `[0..1]`. Use [`clamped`](Self::clamped)
+    /// if needed.
+    #[must_use]
+    pub const fn scaled(self, by: f32) -> ColorRGB {
+        ColorRGB { r: self.r * by, g: self.g * by, b: self.b * by }
+    }
+
+    /// Interpolate this color with another one by `by` amount.
+    ///
+    /// `by` is clamped to `[0..1]`. Each channel is interpolated as-is (i.e. in gamma space),
+    /// matching how the game blends colors.
+    #[must_use]
+    pub const fn linear_interpolated(self, with: ColorRGB, by: f32) -> ColorRGB {
+        let by = by.clamp(0.0, 1.0);
+        ColorRGB {
+            r: self.r + (with.r - self.r) * by,
+            g: self.g + (with.g - self.g) * by,
+            b: self.b + (with.b - self.b) * by
+        }
+    }
+
+    /// Convert the color from gamma (sRGB) space to linear space.
+    ///
+    /// This uses the piecewise sRGB transfer function rather than a plain 2.2 power curve.
+    #[must_use]
+    pub fn to_linear(self) -> ColorRGB {
+        let convert = |c: f32| if c <= 0.04045 {
+            c / 12.92
+        }
+        else {
+            ((c + 0.055) / 1.055).fw_powf(2.4)
+        };
+        ColorRGB { r: convert(self.r), g: convert(self.g), b: convert(self.b) }
+    }
+
+    /// Convert the color from linear space to gamma (sRGB) space.
+    ///
+    /// This is the inverse of [`to_linear`](Self::to_linear).
+    #[must_use]
+    pub fn to_gamma(self) -> ColorRGB {
+        let convert = |c: f32| if c <= 0.0031308 {
+            c * 12.92
+        }
+        else {
+            1.055 * c.fw_powf(1.0 / 2.4) - 0.055
+        };
+        ColorRGB { r: convert(self.r), g: convert(self.g), b: convert(self.b) }
+    }
+
+    /// Get the relative luminance of the color using Rec. 709 weights.
+    ///
+    /// The channels are weighted as-is, so this should be called on a
+    /// [linear](Self::to_linear) color for a physically accurate result.
+    #[must_use]
+    pub const fn luminance(self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    /// Convert the color to a shade of gray with the same [`luminance`](Self::luminance).
+    #[must_use]
+    pub const fn to_grayscale(self) -> ColorRGB {
+        let luminance = self.luminance();
+        ColorRGB { r: luminance, g: luminance, b: luminance }
+    }
+
+    /// Get the mean of all three channels.
+    #[must_use]
+    pub const fn average(self) -> f32 {
+        (self.r + self.g + self.b) / 3.0
+    }
+
+    /// Convert the color to hue, saturation, and value.
+    ///
+    /// Hue is in degrees in the range of `[0..360)`, and saturation and value are in the range of
+    /// `[0..1]`. If the color is a shade of gray, the hue is undefined, so it will be 0.
+    #[must_use]
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+
+        let value = max;
+        let saturation = if max > 0.0 { delta / max } else { 0.0 };
+
+        if delta <= 0.0 {
+            return (0.0, saturation, value)
+        }
+
+        let hue = if max == self.r {
+            (self.g - self.b) / delta
+        }
+        else if max == self.g {
+            (self.b - self.r) / delta + 2.0
+        }
+        else {
+            (self.r - self.g) / delta + 4.0
+        } * 60.0;
+
+        let hue = if hue < 0.0 { hue + 360.0 } else { hue };
+        (hue, saturation, value)
+    }
+
+    /// Convert hue, saturation, and value to a color.
+    ///
+    /// Hue is in degrees and will be wrapped to be within `[0..360)`. Saturation and value are
+    /// expected to be in the range of `[0..1]`.
+    #[must_use]
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> ColorRGB {
+        let h = h.fw_fmod(360.0);
+        let h = if h < 0.0 { h + 360.0 } else { h };
+
+        let chroma = v * s;
+        let sector = h / 60.0;
+        let x = chroma * (1.0 - (sector.fw_fmod(2.0) - 1.0).fw_fabs());
+        let m = v - chroma;
+
+        let (r, g, b) = match sector.fw_floor_to_int() {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x)
+        };
+
+        ColorRGB { r: r + m, g: g + m, b: b + m }
+    }
+
+    /// Composite `over` on top of this color using the given [`BlendMode`].
+    ///
+    /// See [`BlendMode`] for the formula used by each mode.
+    #[must_use]
+    pub const fn blend(self, over: ColorRGB, mode: BlendMode) -> ColorRGB {
+        ColorRGB {
+            r: mode.blend_channel(self.r, over.r),
+            g: mode.blend_channel(self.g, over.g),
+            b: mode.blend_channel(self.b, over.b)
+        }
+    }
+}
+
+/// Determines how [`ColorRGB::blend`] combines two colors.
+///
+/// Each mode is applied to each channel independently, where `a` is the base channel and `b` is
+/// the channel being composited over it.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum BlendMode {
+    /// `b`
+    ///
+    /// This is the default mode.
+    #[default]
+    Normal,
+
+    /// `min(a + b, 1)`
+    Add,
+
+    /// `a * b`
+    Multiply,
+
+    /// `1 - (1 - a) * (1 - b)`
+    Screen,
+
+    /// `2 * a * b` if `a < 0.5`, otherwise `1 - 2 * (1 - a) * (1 - b)`
+    Overlay
+}
+
+impl BlendMode {
+    const fn blend_channel(self, a: f32, b: f32) -> f32 {
+        match self {
+            BlendMode::Normal => b,
+            BlendMode::Add => (a + b).min(1.0),
+            BlendMode::Multiply => a * b,
+            BlendMode::Screen => 1.0 - (1.0 - a) * (1.0 - b),
+            BlendMode::Overlay => if a < 0.5 {
+                2.0 * a * b
+            }
+            else {
+                1.0 - 2.0 * (1.0 - a) * (1.0 - b)
+            }
+        }
+    }
+}
+
+impl Add<ColorRGB> for ColorRGB {
+    type Output = Self;
+
+    fn add(self, value: ColorRGB) -> Self::Output {
+        ColorRGB {
+            r: self.r + value.r,
+            g: self.g + value.g,
+            b: self.b + value.b
+        }
+    }
+}
+
+impl Mul<ColorRGB> for ColorRGB {
+    type Output = Self;
+
+    fn mul(self, value: ColorRGB) -> Self::Output {
+        ColorRGB {
+            r: self.r * value.r,
+            g: self.g * value.g,
+            b: self.b * value.b
+        }
+    }
+}
+
+impl Mul<f32> for ColorRGB {
+    type Output = Self;
+
+    fn mul(self, value: f32) -> Self::Output {
+        self.scaled(value)
+    }
 }
 
 impl From<ColorRGB> for ColorARGB {
@@ -123,6 +471,18 @@ impl From<ColorRGB> for ColorARGB {
     }
 }
 
+impl From<ColorARGB> for Vector4D {
+    fn from(value: ColorARGB) -> Self {
+        value.as_vector4d()
+    }
+}
+
+impl From<Vector4D> for ColorARGB {
+    fn from(value: Vector4D) -> Self {
+        ColorARGB::from_vector4d(value)
+    }
+}
+
 /// Represents a single 32BPP A8R8G8B8 color value.
 ///
 /// Can be represented in binary form as `0xAARRGGBB`.
@@ -130,3 +490,216 @@ impl From<ColorRGB> for ColorARGB {
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
 #[repr(transparent)]
 pub struct Pixel32(pub u32);
+
+const _: () = assert!(size_of::<Pixel32>() == 0x4);
+
+#[cfg(test)]
+mod test {
+    use crate::color::{BlendMode, ColorARGB, ColorRGB, Pixel32};
+    use crate::vector::Vector4D;
+    use crate::util::assert_similar;
+
+    #[test]
+    fn blend() {
+        let color = ColorRGB { r: 0.25, g: 0.5, b: 0.75 };
+
+        assert_eq!(color.blend(ColorRGB::BLACK, BlendMode::Multiply), ColorRGB::BLACK);
+        assert_eq!(ColorRGB::BLACK.blend(color, BlendMode::Multiply), ColorRGB::BLACK);
+        assert_eq!(color.blend(ColorRGB::WHITE, BlendMode::Multiply), color);
+        assert_eq!(ColorRGB::WHITE.blend(color, BlendMode::Multiply), color);
+
+        assert_eq!(color.blend(ColorRGB::BLACK, BlendMode::Screen), color);
+        assert_eq!(color.blend(ColorRGB::WHITE, BlendMode::Screen), ColorRGB::WHITE);
+        assert_eq!(color.blend(color, BlendMode::Screen), ColorRGB { r: 0.4375, g: 0.75, b: 0.9375 });
+
+        assert_eq!(color.blend(ColorRGB::BLACK, BlendMode::Normal), ColorRGB::BLACK);
+        assert_eq!(color.blend(color, BlendMode::Add), ColorRGB { r: 0.5, g: 1.0, b: 1.0 });
+        assert_eq!(color.blend(ColorRGB { r: 0.5, g: 0.5, b: 0.5 }, BlendMode::Overlay), ColorRGB { r: 0.25, g: 0.5, b: 0.75 });
+    }
+
+    #[test]
+    fn hsv() {
+        let assert_hsv = |color: ColorRGB, h: f32, s: f32, v: f32| {
+            let (actual_h, actual_s, actual_v) = color.to_hsv();
+            assert_similar!(actual_h, h, 0.001);
+            assert_similar!(actual_s, s, 0.001);
+            assert_similar!(actual_v, v, 0.001);
+
+            let back = ColorRGB::from_hsv(h, s, v);
+            assert_similar!(back.r, color.r, 0.001);
+            assert_similar!(back.g, color.g, 0.001);
+            assert_similar!(back.b, color.b, 0.001);
+        };
+
+        assert_hsv(ColorRGB { r: 1.0, g: 0.0, b: 0.0 }, 0.0, 1.0, 1.0);
+        assert_hsv(ColorRGB { r: 0.0, g: 1.0, b: 0.0 }, 120.0, 1.0, 1.0);
+        assert_hsv(ColorRGB { r: 0.0, g: 0.0, b: 1.0 }, 240.0, 1.0, 1.0);
+        assert_hsv(ColorRGB { r: 1.0, g: 0.0, b: 0.5 }, 330.0, 1.0, 1.0);
+        assert_hsv(ColorRGB { r: 0.5, g: 0.25, b: 0.25 }, 0.0, 0.5, 0.5);
+        assert_hsv(ColorRGB { r: 0.5, g: 0.5, b: 0.5 }, 0.0, 0.0, 0.5);
+        assert_hsv(ColorRGB::BLACK, 0.0, 0.0, 0.0);
+        assert_hsv(ColorRGB::WHITE, 0.0, 0.0, 1.0);
+
+        // Hue wraps around
+        let green = ColorRGB::from_hsv(120.0, 1.0, 1.0);
+        for h in [480.0, -240.0, 840.0] {
+            let wrapped = ColorRGB::from_hsv(h, 1.0, 1.0);
+            assert_similar!(wrapped.r, green.r, 0.001);
+            assert_similar!(wrapped.g, green.g, 0.001);
+            assert_similar!(wrapped.b, green.b, 0.001);
+        }
+    }
+
+    #[test]
+    fn linear_interpolated() {
+        let from = ColorRGB { r: 0.0, g: 0.5, b: 1.0 };
+        let to = ColorRGB { r: 1.0, g: 0.5, b: 0.0 };
+        assert_eq!(from.linear_interpolated(to, 0.0), from);
+        assert_eq!(from.linear_interpolated(to, 0.5), ColorRGB { r: 0.5, g: 0.5, b: 0.5 });
+        assert_eq!(from.linear_interpolated(to, 1.0), to);
+        assert_eq!(from.linear_interpolated(to, 2.0), to);
+        assert_eq!(from.linear_interpolated(to, -1.0), from);
+
+        let from = ColorARGB { a: 0.0, color: from };
+        let to = ColorARGB { a: 1.0, color: to };
+        assert_eq!(from.linear_interpolated(to, 0.0), from);
+        assert_eq!(from.linear_interpolated(to, 0.5), ColorARGB { a: 0.5, color: ColorRGB { r: 0.5, g: 0.5, b: 0.5 } });
+        assert_eq!(from.linear_interpolated(to, 1.0), to);
+    }
+
+    #[test]
+    fn over() {
+        let red = ColorARGB { a: 0.5, color: ColorRGB { r: 1.0, g: 0.0, b: 0.0 } };
+        let blue = ColorARGB { a: 1.0, color: ColorRGB { r: 0.0, g: 0.0, b: 1.0 } };
+        assert_eq!(red.over(blue), ColorARGB { a: 1.0, color: ColorRGB { r: 0.5, g: 0.0, b: 0.5 } });
+
+        let half_blue = ColorARGB { a: 0.5, ..blue };
+        let result = red.over(half_blue);
+        assert_similar!(result.a, 0.75, 0.0001);
+        assert_similar!(result.color.r, 2.0 / 3.0, 0.0001);
+        assert_similar!(result.color.b, 1.0 / 3.0, 0.0001);
+
+        assert_eq!(blue.over(red), blue);
+        assert_eq!(ColorARGB::new().over(blue), blue);
+        assert_eq!(ColorARGB::new().over(ColorARGB::new()), ColorARGB::new());
+    }
+
+    #[test]
+    fn from_8_bit() {
+        let gray = ColorARGB::from_hex(0xFF808080);
+        assert!(gray.is_valid());
+        assert_eq!(gray.a, 1.0);
+        assert_similar!(gray.color.r, 0.50196, 0.00001);
+        assert_eq!(gray.color.r, gray.color.g);
+        assert_eq!(gray.color.r, gray.color.b);
+
+        assert_eq!(ColorARGB::from_hex(0x00FF0000), ColorARGB { a: 0.0, color: ColorRGB { r: 1.0, g: 0.0, b: 0.0 } });
+        assert_eq!(ColorARGB::from_argb8(255, 255, 255, 255), ColorRGB::WHITE.as_colorargb());
+        assert_eq!(ColorRGB::from_rgb8(0, 0, 0), ColorRGB::BLACK);
+        assert_eq!(ColorARGB::from_hex(0x40C08000).to_pixel32().0, 0x40C08000);
+    }
+
+    #[test]
+    fn luminance() {
+        assert_similar!(ColorRGB::WHITE.luminance(), 1.0, 0.00001);
+        assert_eq!(ColorRGB::BLACK.luminance(), 0.0);
+        assert_eq!(ColorRGB { r: 0.0, g: 1.0, b: 0.0 }.luminance(), 0.7152);
+        assert_eq!(ColorRGB { r: 0.0, g: 0.5, b: 0.0 }.luminance(), 0.3576);
+
+        let gray = ColorRGB { r: 0.0, g: 1.0, b: 0.0 }.to_grayscale();
+        assert_eq!(gray, ColorRGB { r: 0.7152, g: 0.7152, b: 0.7152 });
+        assert_similar!(gray.luminance(), 0.7152, 0.00001);
+
+        assert_eq!(ColorRGB { r: 0.0, g: 1.0, b: 0.5 }.average(), 0.5);
+        assert_eq!(ColorRGB::WHITE.average(), 1.0);
+    }
+
+    #[test]
+    fn from_rgb() {
+        const ORANGE: ColorRGB = ColorRGB::from_rgb(1.0, 0.5, 0.0);
+        assert_eq!(ORANGE, ColorRGB { r: 1.0, g: 0.5, b: 0.0 });
+        assert_eq!(ColorRGB::try_from_rgb(1.0, 0.5, 0.0), Some(ORANGE));
+        assert_eq!(ColorRGB::try_from_rgb(0.0, 0.0, 0.0), Some(ColorRGB::BLACK));
+
+        assert_eq!(ColorRGB::try_from_rgb(1.01, 0.5, 0.0), None);
+        assert_eq!(ColorRGB::try_from_rgb(1.0, -0.5, 0.0), None);
+        assert_eq!(ColorRGB::try_from_rgb(1.0, 0.5, f32::NAN), None);
+    }
+
+    #[test]
+    fn try_to_pixel32() {
+        let valid = ColorARGB { a: 1.0, color: ColorRGB { r: 1.0, g: 0.0, b: 0.0 } };
+        assert_eq!(valid.try_to_pixel32(), Some(Pixel32(0xFFFF0000)));
+        assert_eq!(valid.try_to_pixel32(), Some(valid.to_pixel32()));
+
+        let too_bright = ColorARGB { a: 1.0, color: ColorRGB { r: 1.5, g: 0.0, b: 0.0 } };
+        assert_eq!(too_bright.try_to_pixel32(), None);
+
+        let negative_alpha = ColorARGB { a: -0.25, color: ColorRGB::WHITE };
+        assert_eq!(negative_alpha.try_to_pixel32(), None);
+
+        let negative_channel = ColorARGB { a: 1.0, color: ColorRGB { r: 0.0, g: -0.1, b: 0.0 } };
+        assert_eq!(negative_channel.try_to_pixel32(), None);
+    }
+
+    #[test]
+    fn to_pixel32_clamped() {
+        let hdr = ColorARGB { a: 1.0, color: ColorRGB { r: 1.5, g: 0.5, b: -0.5 } };
+        assert_eq!(hdr.to_pixel32_clamped(), Pixel32(0xFFFF7F00));
+
+        let valid = ColorARGB::from_hex(0x40C08000);
+        assert_eq!(valid.to_pixel32_clamped(), valid.to_pixel32());
+
+        let nan = ColorARGB { a: f32::NAN, color: ColorRGB::WHITE };
+        assert_eq!(nan.to_pixel32_clamped(), Pixel32(0x00FFFFFF));
+    }
+
+    #[test]
+    #[should_panic]
+    fn to_pixel32_invalid() {
+        let _ = ColorARGB { a: 2.0, color: ColorRGB::WHITE }.to_pixel32();
+    }
+
+    #[test]
+    fn arithmetic() {
+        let light = ColorRGB { r: 0.75, g: 0.5, b: 0.25 };
+        let sum = light + light;
+        assert_eq!(sum, ColorRGB { r: 1.5, g: 1.0, b: 0.5 });
+        assert!(!sum.is_valid());
+        assert_eq!(sum.clamped(), ColorRGB { r: 1.0, g: 1.0, b: 0.5 });
+
+        assert_eq!(light * 0.5, ColorRGB { r: 0.375, g: 0.25, b: 0.125 });
+        assert_eq!(light.scaled(0.5), light * 0.5);
+        assert_eq!(light * ColorRGB { r: 0.5, g: 0.5, b: 0.5 }, light * 0.5);
+        assert_eq!(light * ColorRGB::WHITE, light);
+        assert_eq!(light * ColorRGB::BLACK, ColorRGB::BLACK);
+    }
+
+    #[test]
+    fn srgb() {
+        assert_eq!(ColorRGB::BLACK.to_linear(), ColorRGB::BLACK);
+        assert_eq!(ColorRGB::BLACK.to_gamma(), ColorRGB::BLACK);
+        assert_similar!(ColorRGB::WHITE.to_linear().r, 1.0, 0.00001);
+        assert_similar!(ColorRGB::WHITE.to_gamma().r, 1.0, 0.00001);
+
+        // Middle gray in gamma space is about 21.4% in linear space
+        assert_similar!(ColorRGB { r: 0.5, g: 0.5, b: 0.5 }.to_linear().r, 0.21404, 0.0001);
+
+        for x in [0.001, 0.02, 0.1, 0.25, 0.5, 0.75, 0.9] {
+            let color = ColorRGB { r: x, g: x * 0.5, b: 1.0 - x };
+            let round_trip = color.to_gamma().to_linear();
+            assert_similar!(round_trip.r, color.r, 0.0001);
+            assert_similar!(round_trip.g, color.g, 0.0001);
+            assert_similar!(round_trip.b, color.b, 0.0001);
+        }
+    }
+
+    #[test]
+    fn vector4d_conversion() {
+        let color = ColorARGB { a: 0.25, color: ColorRGB { r: 0.5, g: 0.75, b: 1.0 } };
+        let vector = color.as_vector4d();
+        assert_eq!(vector, Vector4D { x: 0.5, y: 0.75, z: 1.0, w: 0.25 });
+        assert_eq!(ColorARGB::from_vector4d(vector), color);
+        assert_eq!(ColorARGB::from(Vector4D::from(color)), color);
+    }
+}
@@ -1,5 +1,8 @@
 //! Contains useful constants and unit conversion functions
 
+use crate::float::FloatOps;
+use crate::vector::Vector3D;
+
 /// Tick rate, measured in Hz.
 pub const TICK_RATE: f32 = 30.0;
 
@@ -57,6 +60,32 @@ pub const fn world_units_to_meters(world_units: f32) -> f32 {
     world_units / WORLD_UNIT_PER_METER
 }
 
+/// Convert a vector in meters to world units.
+///
+/// This is the same as calling [`meters_to_world_units`] on each component.
+#[inline]
+#[must_use]
+pub const fn meters_to_world_units_vec(meters: Vector3D) -> Vector3D {
+    Vector3D {
+        x: meters_to_world_units(meters.x),
+        y: meters_to_world_units(meters.y),
+        z: meters_to_world_units(meters.z)
+    }
+}
+
+/// Convert a vector in world units to meters.
+///
+/// This is the same as calling [`world_units_to_meters`] on each component.
+#[inline]
+#[must_use]
+pub const fn world_units_to_meters_vec(world_units: Vector3D) -> Vector3D {
+    Vector3D {
+        x: world_units_to_meters(world_units.x),
+        y: world_units_to_meters(world_units.y),
+        z: world_units_to_meters(world_units.z)
+    }
+}
+
 /// Convert seconds to ticks.
 #[inline]
 pub const fn seconds_to_ticks(seconds: f32) -> f32 {
@@ -105,6 +134,50 @@ pub const fn reverse_per_seconds_squared_to_per_ticks_squared(ticks_squared: f32
     ticks_squared / TICK_RATE_INVERSE / TICK_RATE_INVERSE
 }
 
+/// Move `current` toward `target` by one tick of exponential decay.
+///
+/// After `half_life_ticks` ticks, the value will have moved halfway to `target`. To use a half-life
+/// in seconds, convert it with [`seconds_to_ticks`] first. If `half_life_ticks` is not positive,
+/// `target` is returned.
+#[must_use]
+pub fn exponential_decay(current: f32, target: f32, half_life_ticks: f32) -> f32 {
+    if half_life_ticks.is_nan() || half_life_ticks <= 0.0 {
+        return target
+    }
+    let remaining = 0.5f32.fw_powf(1.0 / half_life_ticks);
+    target + (current - target) * remaining
+}
+
+/// Get the velocity of a projectile affected by gravity after `ticks` ticks.
+///
+/// Gravity is [`GRAVITY_WORLD_UNITS_PER_TICK_SQUARED`] along -Z, applied once at the end of each
+/// tick. See [`projectile_position`] for the matching position.
+#[must_use]
+pub const fn projectile_velocity(velocity: Vector3D, ticks: f32) -> Vector3D {
+    Vector3D {
+        x: velocity.x,
+        y: velocity.y,
+        z: velocity.z - GRAVITY_WORLD_UNITS_PER_TICK_SQUARED * ticks
+    }
+}
+
+/// Get the position of a projectile affected by gravity after `ticks` ticks.
+///
+/// This is integrated per tick rather than continuously, so it matches stepping the projectile
+/// one tick at a time: each tick, the position is moved by the current velocity, and then gravity
+/// is subtracted from the velocity (see [`projectile_velocity`]). As such, the first tick is not
+/// affected by gravity.
+#[must_use]
+pub const fn projectile_position(initial: Vector3D, velocity: Vector3D, ticks: f32) -> Vector3D {
+    // sum of gravity applied over ticks 0..n is g * n * (n - 1) / 2
+    let fall = GRAVITY_WORLD_UNITS_PER_TICK_SQUARED * ticks * (ticks - 1.0) * 0.5;
+    Vector3D {
+        x: initial.x + velocity.x * ticks,
+        y: initial.y + velocity.y * ticks,
+        z: initial.z + velocity.z * ticks - fall
+    }
+}
+
 /// Length of a detail_object_collection cell in world units.
 pub const DETAIL_OBJECT_WORLD_UNITS_PER_CELL: f32 = 8.0;
 
@@ -116,3 +189,75 @@ pub const XBOX_ADPCM_BLOCK_SAMPLE_COUNT: usize = 64;
 
 /// Size of a 16-bit PCM sample in bytes.
 pub const PCM_SAMPLE_SIZE_BYTES: usize = 2;
+
+#[cfg(test)]
+mod test {
+    use crate::constants::{exponential_decay, meters_to_world_units, meters_to_world_units_vec, projectile_position, projectile_velocity, seconds_to_ticks, world_units_to_meters, world_units_to_meters_vec, GRAVITY_WORLD_UNITS_PER_TICK_SQUARED};
+    use crate::util::assert_similar;
+    use crate::vector::Vector3D;
+
+    #[test]
+    fn exponential_decay_half_life() {
+        let half_life_ticks = seconds_to_ticks(0.5);
+        let mut value = 10.0;
+        for _ in 0..half_life_ticks as usize {
+            value = exponential_decay(value, 2.0, half_life_ticks);
+        }
+        assert_similar!(value, 6.0, 0.0001);
+
+        for _ in 0..half_life_ticks as usize {
+            value = exponential_decay(value, 2.0, half_life_ticks);
+        }
+        assert_similar!(value, 4.0, 0.0001);
+
+        assert_eq!(exponential_decay(10.0, 2.0, 0.0), 2.0);
+        assert_eq!(exponential_decay(2.0, 2.0, 3.0), 2.0);
+    }
+
+    #[test]
+    fn projectile_vertical_throw() {
+        let g = GRAVITY_WORLD_UNITS_PER_TICK_SQUARED;
+        let start = Vector3D { x: 1.0, y: 2.0, z: 3.0 };
+        let velocity = Vector3D { x: 0.0, y: 0.0, z: 0.05 };
+
+        assert_eq!(projectile_position(start, velocity, 0.0), start);
+        assert_eq!(projectile_velocity(velocity, 0.0), velocity);
+        assert_eq!(projectile_position(start, velocity, 1.0), Vector3D { z: 3.05, ..start });
+        assert_eq!(projectile_velocity(velocity, 1.0).z, 0.05 - g);
+
+        // Step it manually and make sure it matches
+        let mut position = start;
+        let mut current_velocity = velocity;
+        for tick in 1..=60 {
+            position += current_velocity;
+            current_velocity.z -= g;
+
+            let expected = projectile_position(start, velocity, tick as f32);
+            assert_eq!(expected.x, position.x);
+            assert_eq!(expected.y, position.y);
+            assert_similar!(expected.z, position.z, 0.0001);
+            assert_similar!(projectile_velocity(velocity, tick as f32).z, current_velocity.z, 0.0001);
+        }
+
+        // It should come back down eventually
+        assert!(projectile_position(start, velocity, 60.0).z < start.z);
+    }
+
+    #[test]
+    fn vector_unit_conversion() {
+        let meters = Vector3D { x: 3.048, y: -1.5, z: 100.0 };
+        let world_units = meters_to_world_units_vec(meters);
+        assert_eq!(world_units.x, meters_to_world_units(meters.x));
+        assert_eq!(world_units.y, meters_to_world_units(meters.y));
+        assert_eq!(world_units.z, meters_to_world_units(meters.z));
+        assert_similar!(world_units.x, 1.0, 0.00001);
+
+        let back = world_units_to_meters_vec(world_units);
+        assert_eq!(back.x, world_units_to_meters(world_units.x));
+        assert_eq!(back.y, world_units_to_meters(world_units.y));
+        assert_eq!(back.z, world_units_to_meters(world_units.z));
+        assert_similar!(back.x, meters.x, 0.00001);
+        assert_similar!(back.y, meters.y, 0.00001);
+        assert_similar!(back.z, meters.z, 0.0001);
+    }
+}
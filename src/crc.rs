@@ -137,10 +137,22 @@ impl CRC32 {
     }
 
     /// Calculate with more bytes.
+    ///
+    /// Data can be fed in any number of chunks, and the result will be the same as if it was all
+    /// passed at once.
     #[inline]
     pub const fn update(&mut self, data: &[u8]) {
         self.crc = crc32(self.crc, data);
     }
+
+    /// Consume the digest, returning the calculated checksum.
+    ///
+    /// This is the same as [`crc`](Self::crc), as no final XOR is applied in order to match Halo.
+    #[inline]
+    #[must_use]
+    pub const fn finalize(self) -> u32 {
+        self.crc
+    }
 }
 
 impl Default for CRC32 {
@@ -149,6 +161,28 @@ impl Default for CRC32 {
     }
 }
 
+/// Calculate the CRC32 of the bytes in one go.
+///
+/// This is the same as using [`CRC32::new`] and [`CRC32::update`].
+#[inline]
+#[must_use]
+pub const fn crc32_of_bytes(data: &[u8]) -> u32 {
+    crc32(u32::MAX, data)
+}
+
+/// Calculate the CRC32 of multiple chunks of bytes as if they were concatenated.
+///
+/// This allows checksumming data in separate buffers (e.g. a header and a body) without copying
+/// them together.
+#[must_use]
+pub fn crc32_of_chunks<'a, I: IntoIterator<Item = &'a [u8]>>(chunks: I) -> u32 {
+    let mut crc = CRC32::new();
+    for chunk in chunks {
+        crc.update(chunk);
+    }
+    crc.finalize()
+}
+
 #[cfg(test)]
 mod test {
     const DATA : &'static [u8] = &[0x02, 0x12, 0x31, 0x24, 0x12, 0x49, 0x78, 0x12, 0x43, 0x78, 0x91, 0x46, 0x15, 0x12, 0x41, 0x23];
@@ -183,4 +217,36 @@ mod test {
         }
         assert_eq!(DATA_CRC32, crc32.crc());
     }
+
+    #[test]
+    fn crc32_streaming_test() {
+        // Split the data at every pair of boundaries, including odd ones and empty chunks.
+        for first in 0..=DATA.len() {
+            for second in first..=DATA.len() {
+                let mut crc32 = CRC32::new();
+                crc32.update(&DATA[..first]);
+                crc32.update(&DATA[first..second]);
+                crc32.update(&DATA[second..]);
+                assert_eq!(DATA_CRC32, crc32.finalize(), "split at {first} and {second}");
+            }
+        }
+
+        assert_eq!(CRC32::default().finalize(), crc32(u32::MAX, &[]));
+    }
+
+    #[test]
+    fn crc32_of_chunks_test() {
+        assert_eq!(DATA_CRC32, crc32_of_bytes(DATA));
+        assert_eq!(DATA_CRC32, crc32_of_chunks([&DATA[..3], &DATA[3..10], &DATA[10..]]));
+        assert_eq!(DATA_CRC32, crc32_of_chunks(DATA.chunks(5)));
+        assert_eq!(DATA_CRC32, crc32_of_chunks([&[][..], DATA, &[]]));
+        assert_eq!(crc32_of_bytes(&[]), crc32_of_chunks([]));
+
+        let header = [0x68u8, 0x65, 0x61, 0x64];
+        let body = [0x62u8, 0x6F, 0x64, 0x79, 0x21];
+        let mut concatenated = [0u8; 9];
+        concatenated[..4].copy_from_slice(&header);
+        concatenated[4..].copy_from_slice(&body);
+        assert_eq!(crc32_of_bytes(&concatenated), crc32_of_chunks([&header[..], &body[..]]));
+    }
 }
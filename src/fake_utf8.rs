@@ -8,13 +8,43 @@ use spin::rwlock::RwLock;
 static CURRENT_REPLACER_MODE: spin::RwLock<UTF8ReplacerMode> = RwLock::new(UTF8ReplacerMode::UTF8);
 
 /// Represents `˚` as `deg` in ASCII
-pub const UTF8_DEGREES: UTF8Replacer = UTF8Replacer::make("˚", "deg");
+pub const UTF8_DEGREES: UTF8Replacer = UTF8Replacer::custom("˚", "deg");
 
 /// Represents `≥` as `>=` in ASCII
-pub const UTF8_GREATER_THAN_OR_EQUAL_TO: UTF8Replacer = UTF8Replacer::make("≥", ">=");
+pub const UTF8_GREATER_THAN_OR_EQUAL_TO: UTF8Replacer = UTF8Replacer::custom("≥", ">=");
 
 /// Represents `≥` as `<=` in ASCII
-pub const UTF8_LESS_THAN_OR_EQUAL_TO: UTF8Replacer = UTF8Replacer::make("≤", "<=");
+pub const UTF8_LESS_THAN_OR_EQUAL_TO: UTF8Replacer = UTF8Replacer::custom("≤", "<=");
+
+/// All built-in replacers, used by [`replace_in`].
+const BUILT_IN_REPLACERS: [UTF8Replacer; 3] = [UTF8_DEGREES, UTF8_GREATER_THAN_OR_EQUAL_TO, UTF8_LESS_THAN_OR_EQUAL_TO];
+
+/// Write `input` to `out`, replacing every glyph of the built-in replacers according to the current
+/// mode.
+///
+/// Everything else is passed through unchanged. In UTF-8 mode, `input` is written as-is.
+pub fn replace_in(input: &str, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+    if matches!(UTF8Replacer::get_mode(), UTF8ReplacerMode::UTF8) {
+        return out.write_str(input)
+    }
+
+    let mut remaining = input;
+    let mut unchanged_len = 0;
+    while unchanged_len < remaining.len() {
+        let rest = &remaining[unchanged_len..];
+        match BUILT_IN_REPLACERS.iter().find(|r| rest.starts_with(r.utf8)) {
+            Some(replacer) => {
+                out.write_str(&remaining[..unchanged_len])?;
+                out.write_str(replacer.ascii)?;
+                remaining = &rest[replacer.utf8.len()..];
+                unchanged_len = 0;
+            },
+            None => unchanged_len += rest.chars().next().map_or(1, char::len_utf8)
+        }
+    }
+
+    out.write_str(remaining)
+}
 
 /// Determines what mode to display [`UTF8Replacer`] in.
 ///
@@ -46,6 +76,16 @@ impl UTF8Replacer {
         *CURRENT_REPLACER_MODE.write() = mode
     }
 
+    /// Set the current replacement mode until the returned guard is dropped.
+    ///
+    /// When the guard is dropped, the mode is restored to what it was before this was called.
+    ///
+    /// Note: Like [`set_mode`](Self::set_mode), this is not immune to race conditions.
+    pub fn scoped_mode(mode: UTF8ReplacerMode) -> ModeGuard {
+        let previous = core::mem::replace(&mut *CURRENT_REPLACER_MODE.write(), mode);
+        ModeGuard { previous }
+    }
+
     /// Get the current replacement mode.
     ///
     /// Note: This is thread-safe, but it is not immune to race conditions.
@@ -53,11 +93,30 @@ impl UTF8Replacer {
         *CURRENT_REPLACER_MODE.read()
     }
 
-    pub(crate) const fn make(utf8: &'static str, ascii: &'static str) -> Self {
+    /// Make a replacer that displays `utf8` in UTF-8 mode and `ascii` in ASCII mode.
+    ///
+    /// Like the built-in replacers, this follows the global mode set with
+    /// [`set_mode`](Self::set_mode).
+    #[must_use]
+    pub const fn custom(utf8: &'static str, ascii: &'static str) -> Self {
         Self { ascii, utf8 }
     }
 }
 
+/// Restores the previous [`UTF8ReplacerMode`] when dropped.
+///
+/// Returned by [`UTF8Replacer::scoped_mode`].
+#[must_use = "the previous mode is restored as soon as the guard is dropped"]
+pub struct ModeGuard {
+    previous: UTF8ReplacerMode
+}
+
+impl Drop for ModeGuard {
+    fn drop(&mut self) {
+        UTF8Replacer::set_mode(self.previous)
+    }
+}
+
 impl Display for UTF8Replacer {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match Self::get_mode() {
@@ -66,3 +125,67 @@ impl Display for UTF8Replacer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::format;
+    use alloc::string::String;
+    use crate::fake_utf8::{replace_in, UTF8Replacer, UTF8ReplacerMode};
+
+    /// Tests that change the global mode must hold this so they do not run at the same time.
+    static MODE_LOCK: spin::Mutex<()> = spin::Mutex::new(());
+
+    #[test]
+    fn custom_replacer() {
+        let _lock = MODE_LOCK.lock();
+        let arrow = UTF8Replacer::custom("→", "->");
+
+        UTF8Replacer::set_mode(UTF8ReplacerMode::ASCII);
+        assert_eq!(format!("a {arrow} b"), "a -> b");
+
+        UTF8Replacer::set_mode(UTF8ReplacerMode::UTF8);
+        assert_eq!(format!("a {arrow} b"), "a → b");
+    }
+
+    #[test]
+    fn replace_in_line() {
+        let _lock = MODE_LOCK.lock();
+        let line = "angle ≥ 90˚ and ≤ 180˚, café";
+        let replace = |input: &str| {
+            let mut out = String::new();
+            replace_in(input, &mut out).unwrap();
+            out
+        };
+
+        UTF8Replacer::set_mode(UTF8ReplacerMode::UTF8);
+        assert_eq!(replace(line), line);
+
+        UTF8Replacer::set_mode(UTF8ReplacerMode::ASCII);
+        assert_eq!(replace(line), "angle >= 90deg and <= 180deg, café");
+        assert_eq!(replace("˚≥≤"), "deg>=<=");
+        assert_eq!(replace(""), "");
+
+        UTF8Replacer::set_mode(UTF8ReplacerMode::UTF8);
+    }
+
+    #[test]
+    fn scoped_mode() {
+        let _lock = MODE_LOCK.lock();
+        let arrow = UTF8Replacer::custom("→", "->");
+
+        UTF8Replacer::set_mode(UTF8ReplacerMode::UTF8);
+        {
+            let _guard = UTF8Replacer::scoped_mode(UTF8ReplacerMode::ASCII);
+            assert_eq!(format!("{arrow}"), "->");
+
+            {
+                let _inner = UTF8Replacer::scoped_mode(UTF8ReplacerMode::UTF8);
+                assert_eq!(format!("{arrow}"), "→");
+            }
+
+            assert_eq!(format!("{arrow}"), "->");
+        }
+        assert_eq!(format!("{arrow}"), "→");
+        assert!(matches!(UTF8Replacer::get_mode(), UTF8ReplacerMode::UTF8));
+    }
+}
@@ -43,6 +43,17 @@ pub trait FloatOps: TrigScalarFloatOps {
     #[must_use]
     fn fw_inverse_sqrt(self) -> Self;
 
+    /// Approximate the inverse square root of a float.
+    ///
+    /// This uses the classic `0x5f3759df` bit hack followed by one iteration of Newton's method,
+    /// which is faster but less accurate than [`fw_inverse_sqrt`](Self::fw_inverse_sqrt), having
+    /// a relative error of up to about 0.175%. Use this only when matching code that uses this
+    /// approximation.
+    ///
+    /// Returns NaN if the float is negative or NaN. `-0.0` is treated the same as `0.0`.
+    #[must_use]
+    fn fw_fast_inverse_sqrt(self) -> Self;
+
     /// Calculate the float to the integer power.
     #[must_use]
     fn fw_powi(self, exponent: i32) -> Self;
@@ -55,6 +66,28 @@ pub trait FloatOps: TrigScalarFloatOps {
     #[must_use]
     fn fw_fabs(self) -> Self;
 
+    /// Calculate the length of the hypotenuse of a right triangle with sides `self` and `other`.
+    ///
+    /// That is, `sqrt(self*self + other*other)`, but without intermediate overflow or underflow.
+    ///
+    /// If either value is infinite, the result is infinite, even if the other value is NaN.
+    #[must_use]
+    fn fw_hypot(self, other: Self) -> Self;
+
+    /// Return the magnitude of `self` with the sign of `sign`.
+    ///
+    /// The sign bit is copied as-is, so `-0.0` is treated as negative and `0.0` is treated as
+    /// positive, and the sign bit of a NaN is also copied.
+    #[must_use]
+    fn fw_copysign(self, sign: Self) -> Self;
+
+    /// Calculate the remainder of `self / divisor`, truncated towards zero.
+    ///
+    /// The result has the same sign as `self`, including signed zero. The result is NaN if `self`
+    /// is infinite, `divisor` is zero, or either value is NaN.
+    #[must_use]
+    fn fw_fmod(self, divisor: Self) -> Self;
+
     /// Calculate the cosine of the float.
     ///
     /// The float is treated as being in radians.
@@ -119,6 +152,17 @@ pub trait FloatOps: TrigScalarFloatOps {
     #[must_use]
     fn fw_floor_to_int(self) -> i32;
 
+    /// Ceil the float.
+    #[must_use]
+    fn fw_ceil_to_int(self) -> i32;
+
+    /// Round the float to the nearest multiple of `step`.
+    ///
+    /// Ties are rounded as in [`fw_round_ties_even_to_int`](Self::fw_round_ties_even_to_int). If
+    /// `step` is close to zero, the float is returned unchanged.
+    #[must_use]
+    fn fw_snap_to(self, step: Self) -> Self;
+
     /// Return true if the given value is within 0.001 of another value.
     #[must_use]
     fn fw_is_close_to(self, to: Self) -> bool;
@@ -127,6 +171,20 @@ pub trait FloatOps: TrigScalarFloatOps {
     #[must_use]
     fn fw_is_closer_to(self, to: Self) -> bool;
 
+    /// Return true if the given value is within `relative_epsilon` times the magnitude of the
+    /// larger value of another value.
+    ///
+    /// Unlike [`fw_is_close_to`](Self::fw_is_close_to), this works for very large and very small
+    /// values. For example, `1000000.0` and `1000050.0` are close with a relative epsilon of
+    /// `0.0001`, but `0.000001` and `0.0000011` are not.
+    ///
+    /// Since the relative tolerance shrinks to nothing near zero, the tolerance is never less than
+    /// an absolute floor of `0.00000001` (1e-8). For example, `0.000000001` and `0.0` are close
+    /// with any relative epsilon, but `0.0000001` and `0.0` are not close with a relative epsilon of
+    /// `0.0001`.
+    #[must_use]
+    fn fw_is_close_to_relative(self, to: Self, relative_epsilon: Self) -> bool;
+
     /// Return true if the given value is non-NaN and non-infinite.
     #[must_use]
     fn fw_is_finite(self) -> bool;
@@ -213,6 +271,18 @@ impl FloatOps for f32 {
         1.0 / self.fw_sqrt()
     }
     #[inline]
+    fn fw_fast_inverse_sqrt(self) -> Self {
+        if self.is_nan() || self < 0.0 {
+            return f32::NAN
+        }
+
+        // abs() clears the sign bit of -0.0, which would otherwise overflow the subtraction
+        let value = self.abs();
+        let half = value * 0.5;
+        let guess = f32::from_bits(0x5f3759df - (value.to_bits() >> 1));
+        guess * (1.5 - half * guess * guess)
+    }
+    #[inline]
     fn fw_powi(self, exponent: i32) -> Self {
         self.fw_powf(exponent as f32)
     }
@@ -225,6 +295,18 @@ impl FloatOps for f32 {
         libm::fabsf(self)
     }
     #[inline]
+    fn fw_hypot(self, other: Self) -> Self {
+        libm::hypotf(self, other)
+    }
+    #[inline]
+    fn fw_copysign(self, sign: Self) -> Self {
+        libm::copysignf(self, sign)
+    }
+    #[inline]
+    fn fw_fmod(self, divisor: Self) -> Self {
+        libm::fmodf(self, divisor)
+    }
+    #[inline]
     fn fw_asin(self) -> Self { libm::asinf(self) }
     #[inline]
     fn fw_acos(self) -> Self { libm::acosf(self) }
@@ -264,6 +346,21 @@ impl FloatOps for f32 {
             }
         }
     }
+    fn fw_ceil_to_int(self) -> i32 {
+        let rounded = self.fw_round_towards_zero_to_int();
+        if self > (rounded as f32) {
+            rounded.saturating_add(1)
+        }
+        else {
+            rounded
+        }
+    }
+    fn fw_snap_to(self, step: Self) -> Self {
+        if step.fw_is_close_to_zero() {
+            return self
+        }
+        (self / step).fw_round_ties_even_to_int() as f32 * step
+    }
     #[inline]
     fn fw_floor(self) -> f32 {
         libm::floorf(self)
@@ -277,6 +374,12 @@ impl FloatOps for f32 {
         (self - to).abs() < 0.0001
     }
     #[inline]
+    fn fw_is_close_to_relative(self, to: Self, relative_epsilon: Self) -> bool {
+        const ABSOLUTE_FLOOR: f32 = 0.00000001;
+        let largest = self.abs().max(to.abs());
+        (self - to).abs() <= (largest * relative_epsilon).max(ABSOLUTE_FLOOR)
+    }
+    #[inline]
     fn fw_is_finite(self) -> bool {
         self.is_finite()
     }
@@ -295,10 +398,27 @@ impl FloatOps for f32 {
     }
 }
 
+/// Calculate `base` to the power of `exponent` by repeated multiplication.
+///
+/// Unlike [`FloatOps::fw_powi`], this can be used in const contexts and does not go through
+/// [`FloatOps::fw_powf`]. Because each multiplication rounds, the result may differ from
+/// [`fw_powf`](FloatOps::fw_powf) by a few ULPs for large exponents or inexact bases, so prefer
+/// this for small exponents (e.g. squaring or cubing).
+#[must_use]
+pub const fn fw_powi_small(base: f32, exponent: u32) -> f32 {
+    let mut result = 1.0;
+    let mut i = 0;
+    while i < exponent {
+        result *= base;
+        i += 1;
+    }
+    result
+}
+
 #[cfg(test)]
 mod test {
     use crate::util::assert_similar;
-    use crate::float::FloatOps;
+    use crate::float::{fw_powi_small, FloatOps};
 
     #[test]
     fn powf() {
@@ -320,6 +440,26 @@ mod test {
         assert_eq!(0.5f32.fw_powi(0), 1.0);
     }
 
+    #[test]
+    fn powi_small() {
+        const RADIUS_SQUARED: f32 = fw_powi_small(3.0, 2);
+        assert_eq!(RADIUS_SQUARED, 9.0);
+
+        for base in [2.0f32, 0.5, -1.5, 0.0] {
+            for exponent in 0..=8 {
+                assert_eq!(fw_powi_small(base, exponent), base.fw_powi(exponent as i32), "{base}^{exponent}");
+            }
+        }
+
+        // Inexact bases may differ slightly due to rounding each multiplication
+        for base in [1.1f32, -0.3, 7.77] {
+            for exponent in 0..=8 {
+                let expected = base.fw_powi(exponent as i32);
+                assert!(fw_powi_small(base, exponent).fw_is_close_to_relative(expected, 0.000001), "{base}^{exponent}");
+            }
+        }
+    }
+
     #[test]
     fn sqrt() {
         assert_eq!(4.0f32.fw_sqrt(), 2.0);
@@ -332,6 +472,88 @@ mod test {
         assert_eq!(0.25f32.fw_inverse_sqrt(), 2.0);
     }
 
+    #[test]
+    fn fast_inverse_sqrt() {
+        for (value, expected) in [(4.0f32, 0.5f32), (0.25, 2.0), (1.0, 1.0), (100.0, 0.1), (2.0, core::f32::consts::FRAC_1_SQRT_2)] {
+            assert_eq!(value.fw_inverse_sqrt(), expected);
+            assert_similar!(value.fw_fast_inverse_sqrt(), expected, expected * 0.00175);
+            assert_ne!(value.fw_fast_inverse_sqrt(), expected);
+        }
+
+        // Known value of the approximation
+        assert_eq!(4.0f32.fw_fast_inverse_sqrt(), 0.49915358);
+
+        assert!((-1.0f32).fw_fast_inverse_sqrt().is_nan());
+        assert!((-0.0001f32).fw_fast_inverse_sqrt().is_nan());
+        assert!(f32::NEG_INFINITY.fw_fast_inverse_sqrt().is_nan());
+        assert!(f32::NAN.fw_fast_inverse_sqrt().is_nan());
+        assert!((-f32::NAN).fw_fast_inverse_sqrt().is_nan());
+        assert_eq!((-0.0f32).fw_fast_inverse_sqrt(), 0.0f32.fw_fast_inverse_sqrt());
+    }
+
+    #[test]
+    fn hypot() {
+        assert_eq!(3.0f32.fw_hypot(4.0), 5.0);
+        assert_eq!((-3.0f32).fw_hypot(-4.0), 5.0);
+        assert_eq!(f32::INFINITY.fw_hypot(f32::NAN), f32::INFINITY);
+        assert_eq!(f32::MAX.fw_hypot(1.0), f32::MAX);
+    }
+
+    #[test]
+    fn copysign() {
+        assert_eq!(2.0f32.fw_copysign(-1.0), -2.0);
+        assert_eq!((-2.0f32).fw_copysign(1.0), 2.0);
+        assert!(1.0f32.fw_copysign(-0.0).is_sign_negative());
+        assert!((-1.0f32).fw_copysign(0.0).is_sign_positive());
+    }
+
+    #[test]
+    fn fmod() {
+        assert_eq!(5.5f32.fw_fmod(2.0), 1.5);
+        assert_eq!((-5.5f32).fw_fmod(2.0), -1.5);
+        assert_eq!(5.5f32.fw_fmod(-2.0), 1.5);
+        assert!((-4.0f32).fw_fmod(2.0).is_sign_negative());
+        assert!(1.0f32.fw_fmod(0.0).is_nan());
+        assert!(f32::INFINITY.fw_fmod(2.0).is_nan());
+    }
+
+    #[test]
+    fn is_close_to_relative() {
+        assert!(1000000.0f32.fw_is_close_to_relative(1000050.0, 0.0001));
+        assert!(!1000000.0f32.fw_is_close_to_relative(1000500.0, 0.0001));
+        assert!(!1000000.0f32.fw_is_close_to(1000001.0), "absolute epsilon is too small for large values");
+
+        assert!(0.000001f32.fw_is_close_to_relative(0.00000100005, 0.0001));
+        assert!(!0.000001f32.fw_is_close_to_relative(0.0000011, 0.0001));
+        assert!(0.000001f32.fw_is_close_to(0.0000011), "absolute epsilon is too large for small values");
+
+        assert!((-5.0f32).fw_is_close_to_relative(-5.0001, 0.0001));
+        assert!(!(-5.0f32).fw_is_close_to_relative(5.0, 0.0001));
+
+        assert!(0.0f32.fw_is_close_to_relative(0.0, 0.0001));
+        assert!(0.0f32.fw_is_close_to_relative(-0.0, 0.0001));
+        assert!(!0.0f32.fw_is_close_to_relative(0.000001, 0.0001));
+
+        // Near zero, the tolerance doesn't go below the absolute floor
+        assert!(0.000000001f32.fw_is_close_to_relative(0.0, 0.0001));
+        assert!(0.0f32.fw_is_close_to_relative(-0.000000001, 0.0001));
+        assert!(0.000000001f32.fw_is_close_to_relative(0.0, 0.0));
+        assert!(!0.0000001f32.fw_is_close_to_relative(0.0, 0.0001));
+
+        // No discontinuity around f32::EPSILON
+        let below = f32::EPSILON * 0.9;
+        let above = f32::EPSILON * 1.1;
+        assert!(!below.fw_is_close_to_relative(0.0, 0.0001));
+        assert!(!below.fw_is_close_to_relative(-below, 0.0001));
+        assert!(below.fw_is_close_to_relative(below + 0.000000005, 0.0001));
+        assert!(above.fw_is_close_to_relative(above + 0.000000005, 0.0001));
+        assert!(!above.fw_is_close_to_relative(above + 0.00000002, 0.0001));
+        assert!(!below.fw_is_close_to_relative(below + 0.00000002, 0.0001));
+        assert!(!0.00000013f32.fw_is_close_to_relative(0.00000011, 0.0001));
+        assert!(!0.00000011f32.fw_is_close_to_relative(-0.00000011, 0.0001));
+        assert!(!f32::NAN.fw_is_close_to_relative(f32::NAN, 0.0001));
+    }
+
     #[test]
     fn sin_cos_tan() {
         assert_similar!(f32::FW_PI.fw_sin(), 0.0, 0.001);
@@ -481,4 +703,59 @@ mod test {
         assert_eq!(4.4f32.fw_floor_to_int(), 4);
         assert_eq!((-4.4f32).fw_floor_to_int(), -5);
     }
+
+    #[test]
+    fn snap_to() {
+        assert_eq!(1.24f32.fw_snap_to(0.25), 1.25);
+        assert_eq!(1.1f32.fw_snap_to(0.25), 1.0);
+        assert_eq!((-1.24f32).fw_snap_to(0.25), -1.25);
+        assert_eq!(7.0f32.fw_snap_to(5.0), 5.0);
+        assert_eq!(8.0f32.fw_snap_to(5.0), 10.0);
+        assert_eq!(1.24f32.fw_snap_to(0.0), 1.24);
+        assert_eq!(1.24f32.fw_snap_to(0.00001), 1.24);
+    }
+
+    #[test]
+    fn fw_ceil_to_int() {
+        assert_eq!(0.0f32.fw_ceil_to_int(), 0);
+        assert_eq!(1.0f32.fw_ceil_to_int(), 1);
+        assert_eq!(2.0f32.fw_ceil_to_int(), 2);
+        assert_eq!(3.0f32.fw_ceil_to_int(), 3);
+        assert_eq!((-1.0f32).fw_ceil_to_int(), -1);
+        assert_eq!((-2.0f32).fw_ceil_to_int(), -2);
+        assert_eq!((-3.0f32).fw_ceil_to_int(), -3);
+
+        assert_eq!(0.5f32.fw_ceil_to_int(), 1);
+        assert_eq!((-0.5f32).fw_ceil_to_int(), 0);
+        assert_eq!(1.5f32.fw_ceil_to_int(), 2);
+        assert_eq!((-1.5f32).fw_ceil_to_int(), -1);
+        assert_eq!(2.5f32.fw_ceil_to_int(), 3);
+        assert_eq!((-2.5f32).fw_ceil_to_int(), -2);
+        assert_eq!(3.5f32.fw_ceil_to_int(), 4);
+        assert_eq!((-3.5f32).fw_ceil_to_int(), -3);
+        assert_eq!(4.5f32.fw_ceil_to_int(), 5);
+        assert_eq!((-4.5f32).fw_ceil_to_int(), -4);
+
+        assert_eq!(0.6f32.fw_ceil_to_int(), 1);
+        assert_eq!((-0.6f32).fw_ceil_to_int(), 0);
+        assert_eq!(1.6f32.fw_ceil_to_int(), 2);
+        assert_eq!((-1.6f32).fw_ceil_to_int(), -1);
+        assert_eq!(2.6f32.fw_ceil_to_int(), 3);
+        assert_eq!((-2.6f32).fw_ceil_to_int(), -2);
+        assert_eq!(3.6f32.fw_ceil_to_int(), 4);
+        assert_eq!((-3.6f32).fw_ceil_to_int(), -3);
+        assert_eq!(4.6f32.fw_ceil_to_int(), 5);
+        assert_eq!((-4.6f32).fw_ceil_to_int(), -4);
+
+        assert_eq!(0.4f32.fw_ceil_to_int(), 1);
+        assert_eq!((-0.4f32).fw_ceil_to_int(), 0);
+        assert_eq!(1.4f32.fw_ceil_to_int(), 2);
+        assert_eq!((-1.4f32).fw_ceil_to_int(), -1);
+        assert_eq!(2.4f32.fw_ceil_to_int(), 3);
+        assert_eq!((-2.4f32).fw_ceil_to_int(), -2);
+        assert_eq!(3.4f32.fw_ceil_to_int(), 4);
+        assert_eq!((-3.4f32).fw_ceil_to_int(), -3);
+        assert_eq!(4.4f32.fw_ceil_to_int(), 5);
+        assert_eq!((-4.4f32).fw_ceil_to_int(), -4);
+    }
 }
@@ -1,6 +1,7 @@
 //! ID and index primitives.
 
 use core::cmp::Ordering;
+use core::num::NonZeroU32;
 
 /// ID primitive
 ///
@@ -10,10 +11,14 @@ use core::cmp::Ordering;
 /// a table's name read in little endian.
 ///
 /// `u32::MAX` ([`NULL_ID`]) is a null ID for any salt type.
-#[derive(Copy, Clone, PartialEq, Debug, Eq)]
+///
+/// When deserialized with serde, the ID is validated with [`ID::from_u32_checked`].
+#[derive(Copy, Clone, PartialEq, Debug, Eq, Hash)]
 #[repr(transparent)]
 pub struct ID<const SALT: u16>(u32);
 
+const _: () = assert!(size_of::<ID<0>>() == 0x4);
+
 /// Represents a null ID for any salt type.
 pub const NULL_ID: u32 = 0xFFFFFFFF;
 
@@ -55,6 +60,20 @@ impl<const SALT: u16> ID<SALT> {
         Some(Self::id_from_index_value(index as u16, created_count))
     }
 
+    /// Iterate through IDs for the indices `start..start + count`.
+    ///
+    /// Like in a tag array, the creation index of each ID is the same as its index (e.g. index 1 is
+    /// `0xE1750001` for a [`TagID`]).
+    ///
+    /// Exactly `count` items are yielded, where `None` is yielded for any index that is too high to
+    /// fit in an ID (see [`from_usize`](Self::from_usize)).
+    pub fn iter_range(start: usize, count: usize) -> impl Iterator<Item = Option<Self>> {
+        (0..count).map(move |offset| {
+            let index = start.checked_add(offset)?;
+            Self::from_usize(index, index as u16)
+        })
+    }
+
     /// Create an ID from a [`u32`].
     #[inline]
     #[must_use] 
@@ -62,6 +81,39 @@ impl<const SALT: u16> ID<SALT> {
         Self(id)
     }
 
+    /// Get the salt of this ID type.
+    #[inline]
+    #[must_use]
+    pub const fn salt() -> u16 {
+        SALT
+    }
+
+    /// Returns true if `id` would be accepted by [`from_u32_checked`](Self::from_u32_checked).
+    ///
+    /// This is useful for validating raw IDs without constructing them. Note that this cannot
+    /// detect IDs of a different salt (see [`from_u32_checked`](Self::from_u32_checked)).
+    #[inline]
+    #[must_use]
+    pub const fn matches_salt(id: u32) -> bool {
+        Self::from_u32_checked(id).is_ok()
+    }
+
+    /// Create an ID from a [`u32`], checking that it is valid for this salt.
+    ///
+    /// Unlike [`from_u32`](Self::from_u32), this returns an [`IdError`] describing why the ID was
+    /// rejected. Null IDs are always accepted.
+    ///
+    /// Since the creation index can offset the salt by any 16-bit value (see
+    /// [`creation_index`](Self::creation_index)), every upper half is valid for some creation
+    /// index, so the salt itself cannot be verified from the ID alone.
+    #[inline]
+    pub const fn from_u32_checked(id: u32) -> Result<Self, IdError> {
+        if id == 0 {
+            return Err(IdError::Zero)
+        }
+        Ok(Self(id))
+    }
+
     /// Returns the binary representation of the ID.
     #[inline]
     #[must_use] 
@@ -69,6 +121,34 @@ impl<const SALT: u16> ID<SALT> {
         self.0
     }
 
+    /// Returns the binary representation of the ID as a [`NonZeroU32`].
+    ///
+    /// The upper bit of the salt is always set for the first 32768 creation indices, so a non-null
+    /// ID is practically never zero. This allows callers to store optional IDs as
+    /// `Option<NonZeroU32>` without any additional space.
+    ///
+    /// Returns `None` if the ID is null (or zero).
+    #[inline]
+    #[must_use]
+    pub const fn as_nonzero(self) -> Option<NonZeroU32> {
+        if self.is_null() {
+            return None
+        }
+        NonZeroU32::new(self.0)
+    }
+
+    /// Create an ID from a [`NonZeroU32`].
+    ///
+    /// This is the reverse of [`as_nonzero`](Self::as_nonzero), where `None` results in a null ID.
+    #[inline]
+    #[must_use]
+    pub const fn from_nonzero(id: Option<NonZeroU32>) -> Self {
+        match id {
+            Some(id) => Self(id.get()),
+            None => Self(NULL_ID)
+        }
+    }
+
     /// Returns true if null.
     #[inline]
     #[must_use] 
@@ -110,8 +190,47 @@ impl<const SALT: u16> ID<SALT> {
     const fn base_identifier() -> u16 {
         SALT | 0x8000
     }
+
+}
+
+#[cfg(feature = "serde")]
+impl<const SALT: u16> serde::Serialize for ID<SALT> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.as_u32())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const SALT: u16> serde::Deserialize<'de> for ID<SALT> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+        match Self::from_u32_checked(id) {
+            Ok(id) => Ok(id),
+            Err(e) => Err(serde::de::Error::custom(e))
+        }
+    }
+}
+
+/// An error returned from [`ID::from_u32_checked`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum IdError {
+    /// The ID is zero.
+    ///
+    /// While this is technically possible for some salts and creation indices, it is almost always
+    /// uninitialized data, and it cannot be represented with [`ID::as_nonzero`].
+    Zero
 }
 
+impl core::fmt::Display for IdError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            IdError::Zero => f.write_str("ID is zero")
+        }
+    }
+}
+
+impl core::error::Error for IdError {}
+
 impl<const SALT: u16> PartialOrd for ID<SALT> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -150,10 +269,12 @@ pub type ScriptNodeID = ID<0x6373>;
 /// Can address up to 65535 elements.
 ///
 /// [`u16::MAX`] is treated as null.
-#[derive(Copy, Clone, PartialEq, Debug, Ord, PartialOrd, Eq)]
+#[derive(Copy, Clone, PartialEq, Debug, Ord, PartialOrd, Eq, Hash)]
 #[repr(transparent)]
 pub struct Index(pub u16);
 
+const _: () = assert!(size_of::<Index>() == 0x2);
+
 impl Index {
     /// Create a null index.
     #[inline]
@@ -201,9 +322,28 @@ impl Default for Index {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Index {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Index {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = <u16 as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(Self::from_usize(index as usize).unwrap_or_default())
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::id::{Index, TagID};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::num::NonZeroU32;
+    use crate::id::{IdError, Index, ScriptNodeID, TagID};
+    use crate::util::hash_of;
 
     #[test]
     fn expected_tag_id_matches() {
@@ -219,4 +359,108 @@ mod test {
         assert!(TagID::from_u32(0xFFFFFFFF).is_null());
         assert!(TagID::from_index(Index::new(), 4).is_null());
     }
+
+    #[test]
+    fn from_u32_checked() {
+        assert_eq!(TagID::from_u32_checked(0xE1750001), Ok(TagID::from_usize(1, 1).unwrap()));
+        assert_eq!(TagID::from_u32_checked(0x61731234), Ok(TagID::from_usize(0x1234, 0x7FFF).unwrap()));
+
+        // Creation indices of 0x8000 and higher are valid
+        assert_eq!(TagID::from_u32_checked(0x61748000), Ok(TagID::from_usize(0x8000, 0x8000).unwrap()));
+        assert_eq!(TagID::from_u32_checked(0x61740001), Ok(TagID::from_usize(1, 0x8000).unwrap()));
+        assert_eq!(TagID::from_u32_checked(0xE173FFFF), Ok(TagID::from_usize(0xFFFF, 0xFFFF).unwrap()));
+        for created_count in [0, 1, 0x7FFF, 0x8000, 0x8001, 0xFFFF] {
+            let id = TagID::from_usize(5, created_count).unwrap();
+            assert_eq!(TagID::from_u32_checked(id.as_u32()), Ok(id));
+        }
+
+        assert!(TagID::from_u32_checked(0xFFFFFFFF).unwrap().is_null());
+        assert_eq!(TagID::from_u32_checked(0), Err(IdError::Zero));
+    }
+
+    #[test]
+    fn id_error_trait() {
+        let boxed: alloc::boxed::Box<dyn core::error::Error> = IdError::Zero.into();
+        assert_eq!(alloc::format!("{boxed}"), "ID is zero");
+    }
+
+    #[test]
+    fn salt() {
+        assert_eq!(TagID::salt(), 0x6174);
+        assert_eq!(ScriptNodeID::salt(), 0x6373);
+
+        for id in [0xE1740000, 0xE1750001, 0x61731234, 0x61740001, 0xA0000001, 0x00001E8C, 0, 0xFFFFFFFF, 0xE3730001, 0x6373FFFF] {
+            assert_eq!(TagID::matches_salt(id), TagID::from_u32_checked(id).is_ok(), "0x{id:08X}");
+            assert_eq!(ScriptNodeID::matches_salt(id), ScriptNodeID::from_u32_checked(id).is_ok(), "0x{id:08X}");
+        }
+
+        assert!(TagID::matches_salt(0xE1750001));
+        assert!(TagID::matches_salt(0x61740001));
+        assert!(ScriptNodeID::matches_salt(0xE3730001));
+        assert!(!ScriptNodeID::matches_salt(0));
+    }
+
+    #[test]
+    fn iter_range() {
+        let ids: Vec<Option<TagID>> = TagID::iter_range(0, 3).collect();
+        assert_eq!(ids, vec![Some(TagID::from_u32(0xE1740000)), Some(TagID::from_u32(0xE1750001)), Some(TagID::from_u32(0xE1760002))]);
+
+        let ids: Vec<Option<TagID>> = TagID::iter_range(0x7FFE, 4).collect();
+        assert_eq!(ids, vec![
+            Some(TagID::from_u32(0x6172_7FFE)),
+            Some(TagID::from_u32(0x6173_7FFF)),
+            Some(TagID::from_u32(0x6174_8000)),
+            Some(TagID::from_u32(0x6175_8001))
+        ]);
+
+        let ids: Vec<Option<TagID>> = TagID::iter_range(0xFFFE, 4).collect();
+        assert_eq!(ids, vec![Some(TagID::from_u32(0xE172_FFFE)), Some(TagID::from_u32(0xE173_FFFF)), None, None]);
+
+        let ids: Vec<Option<TagID>> = TagID::iter_range(usize::MAX, 5).collect();
+        assert_eq!(ids, vec![None; 5]);
+        assert_eq!(TagID::iter_range(5, 0).count(), 0);
+    }
+
+    #[test]
+    fn nonzero_id() {
+        let id = TagID::from_usize(0, 0).unwrap();
+        assert_eq!(id.as_nonzero().map(|i| i.get()), Some(0xE1740000));
+        assert_eq!(TagID::from_nonzero(id.as_nonzero()), id);
+
+        assert_eq!(TagID::new().as_nonzero(), None);
+        assert!(TagID::from_nonzero(None).is_null());
+
+        assert_eq!(size_of::<Option<NonZeroU32>>(), size_of::<TagID>());
+    }
+
+    #[test]
+    fn hash() {
+        assert_eq!(hash_of(&TagID::from_u32(0xE1750001)), hash_of(&TagID::from_usize(1, 1).unwrap()));
+        assert_eq!(hash_of(&TagID::from_u32(0xE1750001)), hash_of(&0xE1750001u32));
+        assert_ne!(hash_of(&TagID::from_usize(1, 1).unwrap()), hash_of(&TagID::from_usize(1, 2).unwrap()));
+        assert_eq!(hash_of(&TagID::new()), hash_of(&TagID::from_index(Index::new(), 4)));
+
+        assert_eq!(hash_of(&Index::from_usize(5).unwrap()), hash_of(&Index(5)));
+        assert_eq!(hash_of(&Index(5)), hash_of(&5u16));
+        assert_ne!(hash_of(&Index(5)), hash_of(&Index(6)));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+        assert_tokens(&TagID::from_usize(1, 1).unwrap(), &[Token::U32(0xE1750001)]);
+        assert_tokens(&TagID::from_usize(0x1234, 0x7FFF).unwrap(), &[Token::U32(0x61731234)]);
+        assert_tokens(&TagID::new(), &[Token::U32(0xFFFFFFFF)]);
+
+        // Creation indices of 0x8000 and higher round-trip
+        assert_tokens(&TagID::from_usize(0x8000, 0x8000).unwrap(), &[Token::U32(0x61748000)]);
+        assert_tokens(&TagID::from_usize(1, 0xFFFF).unwrap(), &[Token::U32(0xE1730001)]);
+
+        assert_de_tokens_error::<TagID>(&[Token::U32(0)], "ID is zero");
+
+        assert_tokens(&Index(5), &[Token::U16(5)]);
+        assert_tokens(&Index::new(), &[Token::U16(0xFFFF)]);
+    }
 }
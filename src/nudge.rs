@@ -1,6 +1,7 @@
-//! Contains the [`fix_decimal_rounding`] function.
+//! Contains the [`fix_decimal_rounding`] function and its variants.
 
 use core::fmt::Write;
+use crate::vector::{Vector2D, Vector3D};
 
 const MAX_SIG_FIGS: usize = 6;
 
@@ -18,7 +19,64 @@ const MAX_SIG_FIGS: usize = 6;
 /// assert_eq!(fix_decimal_rounding(1.74999964237213134765625), 1.75);
 /// assert_eq!(fix_decimal_rounding(999.99994), 1000.0);
 /// ```
+///
+/// This rounds to 6 significant figures. To use a different amount, use
+/// [`fix_decimal_rounding_with_sig_figs`].
+#[must_use]
 pub fn fix_decimal_rounding(input: f32) -> f32 {
+    fix_decimal_rounding_with_sig_figs(input, MAX_SIG_FIGS)
+}
+
+/// Nudges a float like [`fix_decimal_rounding`], rounding it to `sig_figs` significant figures.
+///
+/// If `sig_figs` is 0, or if `input` already has no more than `sig_figs` significant figures,
+/// `input` is returned unchanged.
+///
+/// Example:
+///
+/// ```
+/// use funnel_web::nudge::fix_decimal_rounding_with_sig_figs;
+///
+/// assert_eq!(fix_decimal_rounding_with_sig_figs(33.333332, 4), 33.33);
+/// assert_eq!(fix_decimal_rounding_with_sig_figs(33.333332, 6), 33.3333);
+/// ```
+#[must_use]
+pub fn fix_decimal_rounding_with_sig_figs(input: f32, sig_figs: usize) -> f32 {
+    if !input.is_finite() || sig_figs == 0 {
+        return input
+    }
+
+    let signum = input.signum();
+    let abs = input.abs();
+
+    let mut buf = NudgeBuffer::default();
+    if core::fmt::write(&mut buf, format_args!("{abs}")).is_err() {
+        // The float somehow did not fit in the buffer, so leave it alone.
+        return input
+    }
+
+    match round_to_sig_figs(buf, sig_figs) {
+        Some(f) => (f as f32) * signum,
+        None => input
+    }
+}
+
+/// Nudges a double-precision float like [`fix_decimal_rounding`].
+///
+/// The rounding is done entirely in [`f64`], so this is suitable for values that have not yet been
+/// converted to [`f32`]. Note that the result may differ from calling [`fix_decimal_rounding`] on
+/// the [`f32`] conversion of `input`, since more digits are considered.
+///
+/// Example:
+///
+/// ```
+/// use funnel_web::nudge::fix_decimal_rounding_f64;
+///
+/// assert_eq!(fix_decimal_rounding_f64(1.7500003576278687), 1.75);
+/// assert_eq!(fix_decimal_rounding_f64(999.9999389648438), 1000.0);
+/// ```
+#[must_use]
+pub fn fix_decimal_rounding_f64(input: f64) -> f64 {
     if !input.is_finite() {
         return input
     }
@@ -27,16 +85,29 @@ pub fn fix_decimal_rounding(input: f32) -> f32 {
     let abs = input.abs();
 
     let mut buf = NudgeBuffer::default();
-    core::fmt::write(&mut buf, format_args!("{abs}")).expect("can't write float to buffer");
+    if core::fmt::write(&mut buf, format_args!("{abs}")).is_err() {
+        // The float somehow did not fit in the buffer, so leave it alone.
+        return input
+    }
 
+    match round_to_sig_figs(buf, MAX_SIG_FIGS) {
+        Some(f) => f * signum,
+        None => input
+    }
+}
+
+/// Round the non-negative decimal number written to `buf` to `sig_figs` significant figures.
+///
+/// Returns `None` if the number already has no more than `sig_figs` significant figures.
+fn round_to_sig_figs(buf: NudgeBuffer, sig_figs: usize) -> Option<f64> {
     let str_index = buf.offset;
     let mut buf = buf.buffer;
 
     let written = &mut buf[..str_index];
 
     // First, find the most significant digits
-    if written.len() <= MAX_SIG_FIGS {
-        return input;
+    if written.len() <= sig_figs {
+        return None;
     }
 
     macro_rules! ignore_dot_iter {
@@ -56,18 +127,16 @@ pub fn fix_decimal_rounding(input: f32) -> f32 {
     }
 
     let mut sig_figs_end = str_index;
-    let mut sig_figs_index = 0usize;
     let mut round_up = false;
 
-    for (byte, index) in ignore_dot_iter!(written) {
-        if sig_figs_index == MAX_SIG_FIGS {
+    for (sig_figs_index, (byte, index)) in ignore_dot_iter!(written).enumerate() {
+        if sig_figs_index == sig_figs {
             sig_figs_end = index;
             round_up = *byte >= b'5';
         }
-        if sig_figs_index >= MAX_SIG_FIGS {
+        if sig_figs_index >= sig_figs {
             *byte = b'0';
         }
-        sig_figs_index += 1;
     }
 
     // Do rounding here
@@ -87,10 +156,29 @@ pub fn fix_decimal_rounding(input: f32) -> f32 {
     }
 
     debug_assert!(!prepend_one, "should be no more to prepend");
-    let fstr = core::str::from_utf8(&written).expect("should be utf-8");
+    let fstr = core::str::from_utf8(written).expect("should be utf-8");
 
     let f: f64 = fstr.parse().map_err(|e| panic!("can't parse the float we just made `{fstr}` as a float: {e:?}")).unwrap();
-    (f as f32) * signum
+    Some(f)
+}
+
+/// Nudges each component of a [`Vector3D`] with [`fix_decimal_rounding`].
+#[must_use]
+pub fn fix_decimal_rounding_vector3d(v: Vector3D) -> Vector3D {
+    Vector3D {
+        x: fix_decimal_rounding(v.x),
+        y: fix_decimal_rounding(v.y),
+        z: fix_decimal_rounding(v.z)
+    }
+}
+
+/// Nudges each component of a [`Vector2D`] with [`fix_decimal_rounding`].
+#[must_use]
+pub fn fix_decimal_rounding_vector2d(v: Vector2D) -> Vector2D {
+    Vector2D {
+        x: fix_decimal_rounding(v.x),
+        y: fix_decimal_rounding(v.y)
+    }
 }
 
 struct NudgeBuffer {
@@ -101,7 +189,11 @@ struct NudgeBuffer {
 impl Write for NudgeBuffer {
     fn write_str(&mut self, s: &str) -> core::fmt::Result {
         let s_bytes = s.as_bytes();
-        self.buffer[self.offset..s_bytes.len() + self.offset].copy_from_slice(s_bytes);
+        let destination = self.offset
+            .checked_add(s_bytes.len())
+            .and_then(|end| self.buffer.get_mut(self.offset..end))
+            .ok_or(core::fmt::Error)?;
+        destination.copy_from_slice(s_bytes);
         self.offset += s_bytes.len();
 
         Ok(())
@@ -119,7 +211,9 @@ impl Default for NudgeBuffer {
 
 #[cfg(test)]
 mod test {
-    use crate::nudge::fix_decimal_rounding;
+    use core::fmt::Write;
+    use crate::nudge::{fix_decimal_rounding, fix_decimal_rounding_f64, fix_decimal_rounding_vector2d, fix_decimal_rounding_vector3d, fix_decimal_rounding_with_sig_figs, NudgeBuffer};
+    use crate::vector::{Vector2D, Vector3D};
 
     #[test]
     pub fn test_nudgification() {
@@ -152,4 +246,94 @@ mod test {
         test_nudge(33.3333, 33.333332061767578125);
         test_nudge(-33.3333, -33.333332061767578125);
     }
+
+    #[test]
+    pub fn test_nudgification_f64() {
+        fn test_nudge(expected: f64, value_to_nudge: f64) {
+            assert_ne!(expected, value_to_nudge, "values already equal (FP precision weirdness?)");
+            assert_eq!(expected, fix_decimal_rounding_f64(value_to_nudge), "nudging failed");
+        }
+        fn test_no_nudge(value_to_nudge: f64) {
+            assert_eq!(value_to_nudge, fix_decimal_rounding_f64(value_to_nudge), "nudged when it shouldn't");
+        }
+
+        test_nudge(1.75, 1.75000035762786865234375);
+        test_nudge(1.75, 1.74999964237213134765625);
+        test_nudge(1000.0, 999.99994);
+
+        test_nudge(0.0005, 0.000500000081956386566162109375);
+        test_nudge(0.0005, 0.00049999985);
+        test_nudge(0.001, 0.0009999999);
+        test_nudge(0.0100098, 0.010009766);
+        test_nudge(-0.0100098, -0.010009766);
+
+        test_nudge(1.0, 0.9999995);
+        test_nudge(1.0, 1.0000003);
+
+        test_no_nudge(33.3333);
+        test_no_nudge(1.0);
+        test_no_nudge(1.75);
+        test_no_nudge(0.0100098);
+        test_nudge(33.3333, 33.333332061767578125);
+        test_nudge(-33.3333, -33.333332061767578125);
+
+        // f32 values widened to f64 nudge to the same values
+        test_nudge(1.75, f64::from(1.75000035762786865234375f32));
+        test_nudge(0.1, f64::from(0.1f32));
+
+        test_no_nudge(f64::INFINITY);
+        assert!(fix_decimal_rounding_f64(f64::NAN).is_nan());
+        assert_eq!(fix_decimal_rounding_f64(f64::MAX), 1.79769e308);
+        assert_eq!(fix_decimal_rounding_f64(f64::MIN_POSITIVE), 2.22507e-308);
+    }
+
+    #[test]
+    pub fn test_nudge_sig_figs() {
+        assert_eq!(fix_decimal_rounding_with_sig_figs(33.333332, 4), 33.33);
+        assert_eq!(fix_decimal_rounding_with_sig_figs(33.333332, 6), 33.3333);
+        assert_eq!(fix_decimal_rounding_with_sig_figs(33.333332, 6), fix_decimal_rounding(33.333332));
+        assert_eq!(fix_decimal_rounding_with_sig_figs(-1.2345678, 7), -1.234568);
+        assert_eq!(fix_decimal_rounding_with_sig_figs(999.99994, 4), 1000.0);
+        assert_eq!(fix_decimal_rounding_with_sig_figs(0.00123456, 2), 0.0012);
+
+        // 0 or very large sig figs do nothing
+        assert_eq!(fix_decimal_rounding_with_sig_figs(33.333332, 0), 33.333332);
+        assert_eq!(fix_decimal_rounding_with_sig_figs(33.333332, usize::MAX), 33.333332);
+        assert_eq!(fix_decimal_rounding_with_sig_figs(f32::MAX, 100), f32::MAX);
+        assert_eq!(fix_decimal_rounding_with_sig_figs(f32::MIN_POSITIVE, 1000), f32::MIN_POSITIVE);
+    }
+
+    #[test]
+    pub fn test_nudge_vectors() {
+        let v = Vector3D { x: 1.75000035762786865234375, y: 999.99994, z: -33.333332061767578125 };
+        assert_eq!(fix_decimal_rounding_vector3d(v), Vector3D { x: 1.75, y: 1000.0, z: -33.3333 });
+
+        let v = Vector3D { x: 0.0100098, y: 0.9999995, z: 1.0 };
+        assert_eq!(fix_decimal_rounding_vector3d(v), Vector3D { x: 0.0100098, y: 1.0, z: 1.0 });
+
+        let v = Vector2D { x: 0.00049999985, y: 1.0000003 };
+        assert_eq!(fix_decimal_rounding_vector2d(v), Vector2D { x: 0.0005, y: 1.0 });
+    }
+
+    #[test]
+    pub fn test_nudge_buffer_overflow() {
+        let mut buf = NudgeBuffer::default();
+        let capacity = buf.buffer.len() - buf.offset;
+        let long = "9".repeat(capacity);
+
+        // Exactly filling the buffer is fine.
+        assert!(buf.write_str(&long[..capacity - 1]).is_ok());
+        assert!(buf.write_str("9").is_ok());
+        assert_eq!(buf.offset, buf.buffer.len());
+
+        // Anything more is an error and leaves the buffer alone.
+        assert!(buf.write_str("9").is_err());
+        assert!(buf.write_str("").is_ok());
+        assert_eq!(buf.offset, buf.buffer.len());
+
+        let mut buf = NudgeBuffer::default();
+        assert!(buf.write_str(&long).is_ok());
+        assert!(buf.write_str(&long).is_err());
+        assert!(core::fmt::write(&mut NudgeBuffer::default(), format_args!("{long}{long}")).is_err());
+    }
 }
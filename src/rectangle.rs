@@ -1,5 +1,9 @@
 //! Home of the [`Rectangle`] type.
 
+use core::mem::offset_of;
+use crate::float::FloatOps;
+use crate::vector::{Vector2D, Vector2DInt};
+
 /// Represents a 2D rectangle.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
@@ -11,6 +15,10 @@ pub struct Rectangle {
     pub bottom: i16,
     pub right: i16
 }
+
+const _: () = assert!(size_of::<Rectangle>() == 0x8);
+const _: () = assert!(offset_of!(Rectangle, top) == 0x0 && offset_of!(Rectangle, left) == 0x2 && offset_of!(Rectangle, bottom) == 0x4 && offset_of!(Rectangle, right) == 0x6);
+
 impl Rectangle {
     /// Create a rectangle at (0,0) in the top-left corner with the given width and height.
     ///
@@ -48,6 +56,33 @@ impl Rectangle {
         (self.bottom as i32).wrapping_sub(self.top as i32) as u16
     }
 
+    /// Get the width of the rectangle, keeping the sign.
+    ///
+    /// Unlike [`width`](Self::width), this is negative if the rectangle is inverted (right < left).
+    #[inline]
+    #[must_use]
+    pub const fn signed_width(self) -> i32 {
+        self.right as i32 - self.left as i32
+    }
+
+    /// Get the height of the rectangle, keeping the sign.
+    ///
+    /// Unlike [`height`](Self::height), this is negative if the rectangle is inverted (bottom < top).
+    #[inline]
+    #[must_use]
+    pub const fn signed_height(self) -> i32 {
+        self.bottom as i32 - self.top as i32
+    }
+
+    /// Return true if the rectangle is not inverted (i.e. right >= left and bottom >= top).
+    ///
+    /// Unlike [`is_empty`](Self::is_empty), zero-sized rectangles are valid.
+    #[inline]
+    #[must_use]
+    pub const fn is_valid(self) -> bool {
+        self.right >= self.left && self.bottom >= self.top
+    }
+
     /// Center a rectangle inside a rectangle.
     #[inline]
     #[must_use] 
@@ -62,6 +97,69 @@ impl Rectangle {
         }
     }
 
+    /// Shrink the rectangle so it lies inside `bounds`.
+    ///
+    /// Each edge is clamped to `bounds`, so a rectangle entirely outside of `bounds` results in a
+    /// rectangle with zero width and/or height along the nearest edge of `bounds`.
+    ///
+    /// If `bounds` has a negative width or height, its edges are swapped first.
+    #[must_use]
+    pub fn clamped_to(self, bounds: Rectangle) -> Self {
+        let (top, bottom) = (bounds.top.min(bounds.bottom), bounds.top.max(bounds.bottom));
+        let (left, right) = (bounds.left.min(bounds.right), bounds.left.max(bounds.right));
+        Rectangle {
+            top: self.top.clamp(top, bottom),
+            left: self.left.clamp(left, right),
+            bottom: self.bottom.clamp(top, bottom),
+            right: self.right.clamp(left, right)
+        }
+    }
+
+    /// Move the rectangle by the given offsets.
+    ///
+    /// Like [`centered_inside`](Self::centered_inside), this wraps on overflow.
+    #[inline]
+    #[must_use]
+    pub const fn offset(self, dx: i16, dy: i16) -> Self {
+        Rectangle {
+            top: self.top.wrapping_add(dy),
+            left: self.left.wrapping_add(dx),
+            bottom: self.bottom.wrapping_add(dy),
+            right: self.right.wrapping_add(dx)
+        }
+    }
+
+    /// Multiply each edge by `factor`.
+    ///
+    /// Edges are rounded as in [`fw_round_ties_even_to_int`](FloatOps::fw_round_ties_even_to_int),
+    /// saturating to the range of [`i16`] if out of bounds.
+    #[must_use]
+    pub fn scaled(self, factor: f32) -> Self {
+        let scale = |edge: i16| -> i16 {
+            ((edge as f32) * factor)
+                .fw_round_ties_even_to_int()
+                .clamp(i16::MIN as i32, i16::MAX as i32) as i16
+        };
+        Rectangle {
+            top: scale(self.top),
+            left: scale(self.left),
+            bottom: scale(self.bottom),
+            right: scale(self.right)
+        }
+    }
+
+    /// Get the midpoint of the rectangle.
+    ///
+    /// If the width or height is odd, the midpoint is rounded down.
+    #[inline]
+    #[must_use]
+    pub const fn center(self) -> Vector2DInt {
+        Vector2DInt {
+            x: ((self.left as i32 + self.right as i32) >> 1) as i16,
+            y: ((self.top as i32 + self.bottom as i32) >> 1) as i16
+        }
+    }
+
     /// Get the aspect ratio of the rectangle.
     ///
     /// # Panics
@@ -75,4 +173,219 @@ impl Rectangle {
         assert!(width > 0 && height > 0, "self.width and self.height must be at least 0");
         (width as f32) / (height as f32)
     }
+
+    /// Return true if the pixel is inside the rectangle.
+    ///
+    /// Like typical screen rectangles, the top and left edges are inclusive, while the bottom and
+    /// right edges are exclusive.
+    #[inline]
+    #[must_use]
+    pub const fn contains(self, x: i16, y: i16) -> bool {
+        x >= self.left && x < self.right && y >= self.top && y < self.bottom
+    }
+
+    /// Return true if the point is inside the rectangle.
+    ///
+    /// The point is floored to the pixel containing it before testing with
+    /// [`contains`](Self::contains), so a pixel at `(x, y)` covers everything from `x` up to (but
+    /// not including) `x + 1`.
+    ///
+    /// Returns false if either component is NaN or infinite.
+    #[must_use]
+    pub fn contains_point_f(self, point: Vector2D) -> bool {
+        if !point.x.fw_is_finite() || !point.y.fw_is_finite() {
+            return false
+        }
+        let (Ok(x), Ok(y)) = (i16::try_from(point.x.fw_floor_to_int()), i16::try_from(point.y.fw_floor_to_int())) else {
+            return false
+        };
+        self.contains(x, y)
+    }
+
+    /// Return true if the rectangle has no area (i.e. width or height is 0 or negative).
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.right <= self.left || self.bottom <= self.top
+    }
+
+    /// Get the area covered by both rectangles.
+    ///
+    /// Returns `None` if they do not overlap. Since the bottom and right edges are exclusive,
+    /// rectangles that only touch at an edge do not overlap.
+    #[must_use]
+    pub const fn intersection(self, other: Rectangle) -> Option<Rectangle> {
+        let result = Rectangle {
+            top: if self.top > other.top { self.top } else { other.top },
+            left: if self.left > other.left { self.left } else { other.left },
+            bottom: if self.bottom < other.bottom { self.bottom } else { other.bottom },
+            right: if self.right < other.right { self.right } else { other.right }
+        };
+        if result.is_empty() {
+            None
+        }
+        else {
+            Some(result)
+        }
+    }
+
+    /// Get the smallest rectangle containing both rectangles.
+    ///
+    /// Empty rectangles (see [`is_empty`](Self::is_empty)) are ignored.
+    #[must_use]
+    pub const fn union(self, other: Rectangle) -> Rectangle {
+        if other.is_empty() {
+            return self
+        }
+        if self.is_empty() {
+            return other
+        }
+        Rectangle {
+            top: if self.top < other.top { self.top } else { other.top },
+            left: if self.left < other.left { self.left } else { other.left },
+            bottom: if self.bottom > other.bottom { self.bottom } else { other.bottom },
+            right: if self.right > other.right { self.right } else { other.right }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rectangle::Rectangle;
+    use crate::vector::{Vector2D, Vector2DInt};
+
+    #[test]
+    fn contains() {
+        let rectangle = Rectangle { top: 10, left: 20, bottom: 30, right: 40 };
+        assert!(rectangle.contains(20, 10));
+        assert!(rectangle.contains(39, 29));
+        assert!(!rectangle.contains(40, 10), "right edge is exclusive");
+        assert!(!rectangle.contains(20, 30), "bottom edge is exclusive");
+        assert!(!rectangle.contains(19, 10));
+        assert!(!rectangle.contains(i16::MIN, i16::MAX));
+        assert!(!Rectangle::default().contains(0, 0));
+    }
+
+    #[test]
+    fn signed_size() {
+        let rectangle = Rectangle { top: 10, left: 20, bottom: 30, right: 60 };
+        assert_eq!(rectangle.signed_width(), 40);
+        assert_eq!(rectangle.signed_height(), 20);
+        assert_eq!(rectangle.width(), 40);
+        assert_eq!(rectangle.height(), 20);
+        assert!(rectangle.is_valid());
+
+        let inverted = Rectangle { top: 30, left: 60, bottom: 10, right: 20 };
+        assert_eq!(inverted.signed_width(), -40);
+        assert_eq!(inverted.signed_height(), -20);
+        assert_eq!(inverted.width(), 65496, "unsigned width should still wrap");
+        assert!(!inverted.is_valid());
+        assert!(!Rectangle { top: 0, left: 0, bottom: 10, right: -1 }.is_valid());
+        assert!(!Rectangle { top: 0, left: 0, bottom: -1, right: 10 }.is_valid());
+
+        let extreme = Rectangle { top: i16::MAX, left: i16::MAX, bottom: i16::MIN, right: i16::MIN };
+        assert_eq!(extreme.signed_width(), -65535);
+        assert_eq!(extreme.signed_height(), -65535);
+
+        assert!(Rectangle::default().is_valid(), "zero-sized rectangles are valid");
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = Rectangle { top: 0, left: 0, bottom: 20, right: 20 };
+        let b = Rectangle { top: 10, left: 5, bottom: 30, right: 15 };
+        assert_eq!(a.intersection(b), Some(Rectangle { top: 10, left: 5, bottom: 20, right: 15 }));
+        assert_eq!(a.union(b), Rectangle { top: 0, left: 0, bottom: 30, right: 20 });
+
+        // touching edges
+        let c = Rectangle { top: 0, left: 20, bottom: 20, right: 40 };
+        assert_eq!(a.intersection(c), None);
+        assert_eq!(a.union(c), Rectangle { top: 0, left: 0, bottom: 20, right: 40 });
+
+        // disjoint
+        let d = Rectangle { top: i16::MIN, left: i16::MIN, bottom: -100, right: -100 };
+        assert_eq!(a.intersection(d), None);
+        assert_eq!(d.intersection(a), None);
+        assert_eq!(a.union(d), Rectangle { top: i16::MIN, left: i16::MIN, bottom: 20, right: 20 });
+
+        assert_eq!(a.union(Rectangle::default()), a);
+        assert_eq!(Rectangle::default().union(a), a);
+    }
+
+    #[test]
+    fn clamped_to() {
+        let bounds = Rectangle::from_width_and_height(640, 480);
+
+        let inside = Rectangle { top: 10, left: 20, bottom: 30, right: 40 };
+        assert_eq!(inside.clamped_to(bounds), inside);
+
+        let one_side = Rectangle { top: 10, left: 600, bottom: 30, right: 700 };
+        assert_eq!(one_side.clamped_to(bounds), Rectangle { top: 10, left: 600, bottom: 30, right: 640 });
+
+        let two_sides = Rectangle { top: -50, left: -10, bottom: 30, right: 40 };
+        assert_eq!(two_sides.clamped_to(bounds), Rectangle { top: 0, left: 0, bottom: 30, right: 40 });
+
+        let outside = Rectangle { top: 500, left: 10, bottom: 600, right: 40 };
+        let clamped = outside.clamped_to(bounds);
+        assert_eq!(clamped, Rectangle { top: 480, left: 10, bottom: 480, right: 40 });
+        assert_eq!(clamped.height(), 0);
+
+        // inverted bounds are treated the same as the normal ones
+        let inverted = Rectangle { top: 480, left: 640, bottom: 0, right: 0 };
+        assert_eq!(one_side.clamped_to(inverted), one_side.clamped_to(bounds));
+        assert_eq!(outside.clamped_to(inverted), clamped);
+    }
+
+    #[test]
+    fn offset() {
+        let rectangle = Rectangle { top: 10, left: 20, bottom: 30, right: 40 };
+        assert_eq!(rectangle.offset(5, -10), Rectangle { top: 0, left: 25, bottom: 20, right: 45 });
+        assert_eq!(rectangle.offset(0, 0), rectangle);
+        assert_eq!(Rectangle::from_width_and_height(100, 50).centered_inside(Rectangle::from_width_and_height(200, 100)), Rectangle::from_width_and_height(100, 50).offset(50, 25));
+    }
+
+    #[test]
+    fn scaled() {
+        let rectangle = Rectangle::from_width_and_height(100, 50);
+        let scaled = rectangle.scaled(1.5);
+        assert_eq!(scaled, Rectangle::from_width_and_height(150, 75));
+        assert_eq!(scaled.width(), 150);
+        assert_eq!(scaled.height(), 75);
+
+        // 2.5 and 7.5 round to even
+        assert_eq!(Rectangle { top: 5, left: 1, bottom: 15, right: 3 }.scaled(0.5), Rectangle { top: 2, left: 0, bottom: 8, right: 2 });
+
+        // saturation
+        let large = Rectangle { top: -20000, left: -20000, bottom: 20000, right: 20000 };
+        assert_eq!(large.scaled(2.0), Rectangle { top: i16::MIN, left: i16::MIN, bottom: i16::MAX, right: i16::MAX });
+    }
+
+    #[test]
+    fn center() {
+        let rectangle = Rectangle { top: 10, left: 20, bottom: 30, right: 60 };
+        assert_eq!(rectangle.center(), Vector2DInt { x: 40, y: 20 });
+        assert_eq!(Rectangle { top: -10, left: -21, bottom: 1, right: 0 }.center(), Vector2DInt { x: -11, y: -5 }, "should round down");
+        assert_eq!(Rectangle { top: i16::MIN, left: i16::MIN, bottom: i16::MAX, right: i16::MAX }.center(), Vector2DInt { x: -1, y: -1 });
+    }
+
+    #[test]
+    fn contains_point_f() {
+        let rectangle = Rectangle { top: 10, left: 20, bottom: 30, right: 40 };
+        assert!(rectangle.contains_point_f(Vector2D { x: 20.0, y: 10.0 }));
+        assert!(rectangle.contains_point_f(Vector2D { x: 39.99, y: 29.99 }));
+        assert!(rectangle.contains_point_f(Vector2D { x: 20.5, y: 10.5 }));
+        assert!(!rectangle.contains_point_f(Vector2D { x: 40.0, y: 20.0 }), "right edge is exclusive");
+        assert!(!rectangle.contains_point_f(Vector2D { x: 30.0, y: 30.0 }), "bottom edge is exclusive");
+        assert!(!rectangle.contains_point_f(Vector2D { x: 19.99, y: 20.0 }), "should floor to 19");
+        assert!(!rectangle.contains_point_f(Vector2D { x: 30.0, y: 9.5 }), "should floor to 9");
+        assert!(!rectangle.contains_point_f(Vector2D { x: f32::NAN, y: 20.0 }));
+
+        // NaN must not floor to a pixel inside a rectangle containing the origin
+        let screen = Rectangle::from_width_and_height(640, 480);
+        assert!(screen.contains_point_f(Vector2D { x: 0.0, y: 0.0 }));
+        assert!(!screen.contains_point_f(Vector2D { x: f32::NAN, y: f32::NAN }));
+        assert!(!screen.contains_point_f(Vector2D { x: f32::NAN, y: 0.0 }));
+        assert!(!screen.contains_point_f(Vector2D { x: 0.0, y: -f32::NAN }));
+        assert!(!screen.contains_point_f(Vector2D { x: f32::INFINITY, y: 0.0 }));
+    }
 }
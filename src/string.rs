@@ -3,6 +3,7 @@
 use alloc::string::String;
 use core::ffi::{c_char, CStr};
 use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
 
 /// Null-terminated multi character ASCII string.
 ///
@@ -92,6 +93,174 @@ impl<const LEN: usize> ASCIIString<LEN> {
         Self::from_bytes(bytes)
     }
 
+    /// Instantiate an ASCIIString from a string, truncating it instead of failing.
+    ///
+    /// Bytes are copied until the string is full (i.e. `LEN - 1` bytes) or a control or non-ASCII
+    /// character is found, and everything after that is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `LEN == 0`
+    #[must_use]
+    pub const fn from_str_lossy(str: &str) -> Self {
+        assert!(LEN > 0, "LEN must be nonzero");
+
+        let str_bytes = str.as_bytes();
+        let mut bytes = [0u8; LEN];
+        let mut q = 0;
+        while q < str_bytes.len() && q < LEN - 1 {
+            let byte = str_bytes[q];
+            if !byte.is_ascii() || byte.is_ascii_control() {
+                break
+            }
+            bytes[q] = byte;
+            q += 1;
+        }
+
+        Self(bytes)
+    }
+
+    /// Instantiate an ASCIIString from a C string.
+    ///
+    /// Returns `None` if the string is longer than the maximum length or contains control or
+    /// non-ASCII characters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `LEN == 0`
+    #[must_use]
+    pub const fn from_cstr(cstr: &CStr) -> Option<Self> {
+        assert!(LEN > 0, "LEN must be nonzero");
+
+        let str_bytes = cstr.to_bytes();
+        let len = str_bytes.len();
+        if len >= LEN {
+            return None
+        }
+
+        let mut bytes = [0u8; LEN];
+        let mut q = 0;
+        while q < len {
+            bytes[q] = str_bytes[q];
+            q += 1;
+        }
+
+        Self::from_bytes(bytes)
+    }
+
+    /// Append a string to the end of the string.
+    ///
+    /// Returns `Err` if the resulting string would be too long or `str` contains control or
+    /// non-ASCII characters, in which case the string is left unmodified.
+    #[expect(clippy::result_unit_err)]
+    pub const fn push_str(&mut self, str: &str) -> Result<(), ()> {
+        let str_bytes = str.as_bytes();
+        let start = self.string_len();
+        let len = str_bytes.len();
+        if len >= LEN - start {
+            return Err(())
+        }
+
+        let mut q = 0;
+        while q < len {
+            let byte = str_bytes[q];
+            if !byte.is_ascii() || byte.is_ascii_control() {
+                return Err(())
+            }
+            q += 1;
+        }
+
+        let mut q = 0;
+        while q < len {
+            self.0[start + q] = str_bytes[q];
+            q += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Join this string and `other` into a new string with a capacity of `OUT`.
+    ///
+    /// Returns `None` if the combined string is too long to fit (i.e. it is not less than `OUT`).
+    #[must_use]
+    pub const fn concat<const OUT: usize>(&self, other: &ASCIIString<LEN>) -> Option<ASCIIString<OUT>> {
+        let first = self.content_bytes();
+        let second = other.content_bytes();
+        if first.len() + second.len() >= OUT {
+            return None
+        }
+
+        let mut bytes = [0u8; OUT];
+        let mut q = 0;
+        while q < first.len() {
+            bytes[q] = first[q];
+            q += 1;
+        }
+
+        let mut q = 0;
+        while q < second.len() {
+            bytes[first.len() + q] = second[q];
+            q += 1;
+        }
+
+        Some(ASCIIString(bytes))
+    }
+
+    /// Shorten the string to `new_len` characters.
+    ///
+    /// All bytes after the new end of the string are zeroed out. This does nothing if `new_len` is
+    /// greater than or equal to the current length.
+    pub const fn truncate(&mut self, new_len: usize) {
+        let len = self.string_len();
+        let mut q = new_len;
+        while q < len {
+            self.0[q] = 0;
+            q += 1;
+        }
+    }
+
+    /// Return `true` if the string starts with `prefix`.
+    ///
+    /// This is case-sensitive.
+    #[must_use]
+    pub const fn starts_with(&self, prefix: &str) -> bool {
+        let content = self.content_bytes();
+        let prefix = prefix.as_bytes();
+        prefix.len() <= content.len() && bytes_equal_at(content, 0, prefix)
+    }
+
+    /// Return `true` if the string ends with `suffix`.
+    ///
+    /// This is case-sensitive.
+    #[must_use]
+    pub const fn ends_with(&self, suffix: &str) -> bool {
+        let content = self.content_bytes();
+        let suffix = suffix.as_bytes();
+        suffix.len() <= content.len() && bytes_equal_at(content, content.len() - suffix.len(), suffix)
+    }
+
+    /// Return `true` if `needle` appears anywhere in the string.
+    ///
+    /// This is case-sensitive.
+    #[must_use]
+    pub const fn contains(&self, needle: &str) -> bool {
+        let content = self.content_bytes();
+        let needle = needle.as_bytes();
+        if needle.len() > content.len() {
+            return false
+        }
+
+        let mut start = 0;
+        while start <= content.len() - needle.len() {
+            if bytes_equal_at(content, start, needle) {
+                return true
+            }
+            start += 1;
+        }
+
+        false
+    }
+
     /// Get the full bytes buffer.
     #[inline]
     #[must_use] 
@@ -99,6 +268,20 @@ impl<const LEN: usize> ASCIIString<LEN> {
         &self.0
     }
 
+    /// Get the bytes of the string, excluding the null terminator and everything after it.
+    #[inline]
+    #[must_use]
+    pub const fn content_bytes(&self) -> &[u8] {
+        self.as_cstr().to_bytes()
+    }
+
+    /// Iterate through the characters of the string, excluding the null terminator and
+    /// everything after it.
+    #[inline]
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.content_bytes().iter().map(|b| *b as char)
+    }
+
     /// Get the length of the string.
     #[must_use] 
     pub const fn string_len(&self) -> usize {
@@ -128,6 +311,20 @@ impl<const LEN: usize> ASCIIString<LEN> {
     }
 }
 
+/// Return `true` if `bytes` contains `other` at `start`.
+///
+/// `start + other.len()` must not exceed `bytes.len()`.
+const fn bytes_equal_at(bytes: &[u8], start: usize, other: &[u8]) -> bool {
+    let mut q = 0;
+    while q < other.len() {
+        if bytes[start + q] != other[q] {
+            return false
+        }
+        q += 1;
+    }
+    true
+}
+
 impl<const LEN: usize> Default for ASCIIString<LEN> {
     fn default() -> Self {
         Self::new()
@@ -152,6 +349,13 @@ impl<const LEN: usize> Debug for ASCIIString<LEN> {
     }
 }
 
+impl<const LEN: usize> Hash for ASCIIString<LEN> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Only hash the string itself, as the padding is always zeroed out
+        self.as_str().hash(state)
+    }
+}
+
 impl<const LEN: usize> AsRef<str> for ASCIIString<LEN> {
     fn as_ref(&self) -> &str {
         self.as_str()
@@ -205,6 +409,7 @@ mod test {
     use alloc::string::ToString;
     use core::iter::once;
     use crate::string::{ASCIIString, String32};
+    use crate::util::hash_of;
 
     type String8 = ASCIIString<8>;
 
@@ -232,6 +437,19 @@ mod test {
         assert_eq!(String32::from_str("süß"), None, "ü and ß are not ASCII characters");
     }
 
+    #[test]
+    fn from_str_lossy() {
+        assert_eq!(String8::from_str_lossy("abc"), "abc");
+        assert_eq!(String8::from_str_lossy(""), "");
+        assert_eq!(String8::from_str_lossy("1234567"), "1234567");
+        assert_eq!(String8::from_str_lossy("123456789"), "1234567", "should be truncated to seven characters");
+        assert_eq!(String8::from_str_lossy("123456789").bytes(), b"1234567\0");
+
+        assert_eq!(String8::from_str_lossy("ab\ncd"), "ab", "should stop at the control character");
+        assert_eq!(String8::from_str_lossy("ab\0cd").bytes(), b"ab\0\0\0\0\0\0");
+        assert_eq!(String32::from_str_lossy("jalapeño"), "jalape", "should stop at the non-ASCII character");
+    }
+
     #[test]
     fn from_bytes() {
         // all bytes after the first null byte get zeroed out
@@ -261,4 +479,122 @@ mod test {
             assert_eq!(String8::from_bytes([i, b'b', b'c', b'd', 0x00, 0x00, 0x00, 0x00]), None, "0x{i:02X} is not ASCII and thus should not be permitted in ASCIIString but it was");
         }
     }
+
+    #[test]
+    fn push_str() {
+        let mut string = String8::from_str("abc").unwrap();
+        assert_eq!(string.push_str("def"), Ok(()));
+        assert_eq!(string, "abcdef");
+        assert_eq!(string.push_str("gh"), Err(()), "ASCIIString<8> should only allow at most seven characters");
+        assert_eq!(string, "abcdef", "failed push should not modify the string");
+        assert_eq!(string.push_str("\n"), Err(()), "control characters are banned");
+        assert_eq!(string.push_str("ñ"), Err(()), "non-ASCII characters are banned");
+        assert_eq!(string.push_str("\0"), Err(()), "null characters are banned");
+        assert_eq!(string.push_str("g"), Ok(()));
+        assert_eq!(string, "abcdefg");
+        assert_eq!(string.bytes(), b"abcdefg\0");
+        assert_eq!(string.push_str(""), Ok(()), "appending nothing always fits");
+    }
+
+    #[test]
+    fn concat() {
+        let tag = String8::from_str("weapons").unwrap();
+        let subtag = String8::from_str("\\rifle").unwrap();
+        let joined: String32 = tag.concat(&subtag).unwrap();
+        assert_eq!(joined, "weapons\\rifle");
+
+        let joined: String32 = tag.concat(&String8::new()).unwrap();
+        assert_eq!(joined, "weapons");
+        let joined: String32 = String8::new().concat(&String8::new()).unwrap();
+        assert_eq!(joined, "");
+
+        // 7 + 6 characters does not fit in ASCIIString<13>, but it does in ASCIIString<14>
+        assert_eq!(tag.concat::<13>(&subtag), None);
+        assert_eq!(tag.concat::<14>(&subtag).unwrap(), "weapons\\rifle");
+        assert_eq!(tag.concat::<8>(&subtag), None);
+    }
+
+    #[test]
+    fn search() {
+        let string = String32::from_str("weapons\\pistol\\pistol").unwrap();
+
+        assert!(string.starts_with("weapons\\"));
+        assert!(string.starts_with("weapons\\pistol\\pistol"));
+        assert!(!string.starts_with("Weapons"), "should be case-sensitive");
+        assert!(!string.starts_with("pistol"));
+        assert!(!string.starts_with("weapons\\pistol\\pistol!"));
+
+        assert!(string.ends_with("\\pistol"));
+        assert!(string.ends_with("weapons\\pistol\\pistol"));
+        assert!(!string.ends_with("PISTOL"), "should be case-sensitive");
+        assert!(!string.ends_with("weapons"));
+        assert!(!string.ends_with("!weapons\\pistol\\pistol"));
+
+        assert!(string.contains("pistol\\"));
+        assert!(string.contains("s\\p"));
+        assert!(!string.contains("Pistol"), "should be case-sensitive");
+        assert!(!string.contains("rifle"));
+        assert!(!string.contains("weapons\\pistol\\pistol\\pistol"));
+
+        // Empty needles always match
+        assert!(string.starts_with(""));
+        assert!(string.ends_with(""));
+        assert!(string.contains(""));
+
+        let empty = String8::new();
+        assert!(empty.starts_with(""));
+        assert!(empty.ends_with(""));
+        assert!(empty.contains(""));
+        assert!(!empty.starts_with("a"));
+        assert!(!empty.ends_with("a"));
+        assert!(!empty.contains("a"));
+    }
+
+    #[test]
+    fn truncate() {
+        let mut string = String8::from_str("abcdefg").unwrap();
+        string.truncate(10);
+        assert_eq!(string, "abcdefg");
+        string.truncate(3);
+        assert_eq!(string, "abc");
+        assert_eq!(string.bytes(), &[b'a', b'b', b'c', 0, 0, 0, 0, 0], "should be zeroed out");
+        string.truncate(0);
+        assert_eq!(string.bytes(), &[0u8; 8], "should be zeroed out");
+    }
+
+    #[test]
+    fn from_cstr() {
+        assert_eq!(String8::from_cstr(c"1234567").unwrap(), "1234567", "ASCIIString<8> should support seven characters");
+        assert_eq!(String8::from_cstr(c"").unwrap(), "");
+        assert_eq!(String8::from_cstr(c"12345678"), None, "ASCIIString<8> should only allow at most seven characters");
+        assert_eq!(String8::from_cstr(c"abc\xFFdef"), None, "0xFF is not ASCII");
+        assert_eq!(String8::from_cstr(c"abc\x1Fdef"), None, "0x1F is a banned control character");
+    }
+
+    #[test]
+    fn hash() {
+        let a = String8::from_bytes([b'a', b'b', b'c', 0x00, b'x', b'y', 0x00, 0x00]).unwrap();
+        let b = String8::from_bytes([b'a', b'b', b'c', 0x00, 0x00, 0x00, b'z', 0x00]).unwrap();
+        let c = String8::from_str("abc").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(hash_of(&a), hash_of(&c));
+        assert_eq!(hash_of(&a), hash_of(&"abc"), "should hash the same as the equivalent str");
+        assert_ne!(hash_of(&a), hash_of(&String8::from_str("abd").unwrap()));
+    }
+
+    #[test]
+    fn content() {
+        let string = String8::from_bytes([b'a', b'b', b'c', 0x00, b'x', 0x00, 0x00, 0x00]).unwrap();
+        assert_eq!(string.content_bytes(), b"abc");
+        assert_eq!(string.content_bytes().len(), string.string_len());
+        assert!(string.chars().eq("abc".chars()));
+
+        assert_eq!(String8::new().content_bytes(), b"");
+        assert_eq!(String8::new().chars().next(), None);
+
+        let full = String8::from_str("1234567").unwrap();
+        assert_eq!(full.content_bytes().len(), full.string_len());
+        assert!(full.chars().eq("1234567".chars()));
+    }
 }
@@ -17,3 +17,24 @@ macro_rules! assert_similar {
 
 #[cfg(test)]
 pub(crate) use assert_similar;
+
+/// Hash a value with a deterministic (FNV-1a) hasher.
+#[cfg(test)]
+pub(crate) fn hash_of<T: core::hash::Hash>(value: &T) -> u64 {
+    struct FnvHasher(u64);
+
+    impl core::hash::Hasher for FnvHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            for b in bytes {
+                self.0 = (self.0 ^ (*b as u64)).wrapping_mul(0x100000001B3);
+            }
+        }
+    }
+
+    let mut hasher = FnvHasher(0xCBF29CE484222325);
+    value.hash(&mut hasher);
+    core::hash::Hasher::finish(&hasher)
+}
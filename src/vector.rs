@@ -2,10 +2,11 @@
 
 use core::cmp::Ordering;
 use core::fmt::{Debug, Display, Formatter};
-use core::mem::transmute;
+use core::mem::offset_of;
 use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use crate::fake_utf8::UTF8_DEGREES;
 use crate::float::{FloatOps, TrigScalarFloatOps};
+use crate::rectangle::Rectangle;
 
 /// A matrix with just the forward and up components.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -17,9 +18,37 @@ pub struct Matrix2x3 {
     pub up: Vector3D
 }
 
+const _: () = assert!(size_of::<Matrix2x3>() == 0x18);
+const _: () = assert!(offset_of!(Matrix2x3, forward) == 0x0 && offset_of!(Matrix2x3, up) == 0xC);
+
 impl Matrix2x3 {
     /// Identity matrix.
     pub const IDENTITY: Matrix2x3 = Matrix2x3 { forward: Vector3D { x: 1.0, y: 0.0, z: 0.0 }, up: Vector3D { x: 0.0, y: 0.0, z: 1.0 } };
+
+    /// Convert to a [`Matrix3x3`], deriving `left` as `up × forward`.
+    ///
+    /// This makes the basis right-handed (i.e. `forward × left = up`), matching [`Matrix3x3`]. The
+    /// `forward` and `up` vectors are assumed to be orthonormal; otherwise, `left` will not be a
+    /// unit vector.
+    #[must_use]
+    pub const fn as_matrix3x3(self) -> Matrix3x3 {
+        Matrix3x3::from_vectors(self.forward, self.up)
+    }
+
+    /// Transform the vector.
+    ///
+    /// This is the same as [`Matrix3x3::transform_vector`] with the matrix from
+    /// [`as_matrix3x3`](Self::as_matrix3x3).
+    #[must_use]
+    pub const fn transform_vector(&self, vector: Vector3D) -> Vector3D {
+        self.as_matrix3x3().transform_vector(vector)
+    }
+}
+
+impl From<Matrix2x3> for Matrix3x3 {
+    fn from(value: Matrix2x3) -> Self {
+        value.as_matrix3x3()
+    }
 }
 
 /// A full 3x3 matrix.
@@ -33,6 +62,9 @@ pub struct Matrix3x3 {
     pub up: Vector3D
 }
 
+const _: () = assert!(size_of::<Matrix3x3>() == 0x24);
+const _: () = assert!(offset_of!(Matrix3x3, forward) == 0x0 && offset_of!(Matrix3x3, left) == 0xC && offset_of!(Matrix3x3, up) == 0x18);
+
 impl Matrix3x3 {
     /// Identity matrix.
     pub const IDENTITY: Matrix3x3 = Matrix3x3 {
@@ -73,12 +105,72 @@ impl Matrix3x3 {
         }
     }
 
+    /// Instantiate a rotation matrix that rotates `angle` around `axis`.
+    ///
+    /// This follows the right-hand rule, so rotating [`forward`](Self::forward) 90° around
+    /// [`up`](Self::up) gives [`left`](Self::left). The axis is normalized first; if it is close to
+    /// zero, [`Matrix3x3::IDENTITY`] is returned.
+    #[must_use]
+    pub fn from_axis_angle(axis: Vector3D, angle: Angle) -> Matrix3x3 {
+        let Some(k) = axis.normalized() else {
+            return Matrix3x3::IDENTITY
+        };
+
+        let radians = angle.radians();
+        let c = radians.fw_cos();
+        let s = radians.fw_sin();
+        let t = 1.0 - c;
+
+        Matrix3x3 {
+            forward: Vector3D {
+                x: c + k.x * k.x * t,
+                y: k.z * s + k.x * k.y * t,
+                z: -k.y * s + k.x * k.z * t
+            },
+            left: Vector3D {
+                x: -k.z * s + k.x * k.y * t,
+                y: c + k.y * k.y * t,
+                z: k.x * s + k.y * k.z * t
+            },
+            up: Vector3D {
+                x: k.y * s + k.x * k.z * t,
+                y: -k.x * s + k.y * k.z * t,
+                z: c + k.z * k.z * t
+            }
+        }
+    }
+
+    /// Instantiate a rotation matrix facing `forward`, using `up` as a reference.
+    ///
+    /// The resulting [`forward`](Self::forward) is `forward` normalized, [`left`](Self::left) is
+    /// `up × forward` normalized, and [`up`](Self::up) is recomputed as `forward × left` so all
+    /// three are perpendicular.
+    ///
+    /// Returns `None` if `forward` is close to zero or parallel to `up`.
+    #[must_use]
+    pub fn look_at(forward: Vector3D, up: Vector3D) -> Option<Matrix3x3> {
+        let forward = forward.normalized()?;
+        let left = up.cross_product(forward).normalized()?;
+        Some(Matrix3x3 { forward, left, up: forward.cross_product(left) })
+    }
+
     /// Interpolate this matrix by another one by `by` amount.
     #[must_use]
     pub fn interpolated(self, with: Matrix3x3, by: f32) -> Matrix3x3 {
         self.as_quaternion().interpolated(with.as_quaternion(), by).into()
     }
 
+    /// Raise the matrix to the power of `t`, scaling its rotation by `t`.
+    ///
+    /// For example, `0.5` returns half of the rotation, and `2.0` applies the rotation twice.
+    ///
+    /// The matrix is assumed to be a rotation matrix (orthonormal with no scaling or skewing), as
+    /// it is converted to a [`Quaternion`] (see [`Quaternion::powf`]) and back.
+    #[must_use]
+    pub fn powf(self, t: f32) -> Matrix3x3 {
+        self.as_quaternion().powf(t).as_matrix()
+    }
+
     /// Convert the matrix to a quaternion.
     #[must_use]
     pub fn as_quaternion(&self) -> Quaternion {
@@ -140,21 +232,44 @@ impl Matrix3x3 {
         }
     }
 
+    /// Calculate the determinant of the matrix.
+    ///
+    /// This is +1 for a right-handed rotation matrix.
+    #[must_use]
+    pub const fn determinant(self) -> f32 {
+        self.forward.x * self.left.y * self.up.z +
+        self.forward.y * self.left.z * self.up.x +
+        self.forward.z * self.left.x * self.up.y -
+        self.forward.x * self.left.z * self.up.y -
+        self.forward.y * self.left.x * self.up.z -
+        self.forward.z * self.left.y * self.up.x
+    }
+
+    /// Return the matrix made orthonormal with Gram-Schmidt.
+    ///
+    /// `forward` is normalized, `up` has its `forward` component removed and is normalized, and
+    /// `left` is derived as `up × forward`, resulting in a right-handed rotation matrix. This is
+    /// useful before converting a matrix that has drifted to a [`Quaternion`].
+    ///
+    /// If a basis can't be recovered (i.e. `forward` is zero or `up` is parallel to it),
+    /// [`Matrix3x3::IDENTITY`] is returned.
+    #[must_use]
+    pub fn orthonormalized(self) -> Matrix3x3 {
+        let Some(forward) = self.forward.normalized() else {
+            return Self::IDENTITY
+        };
+        let Some(up) = (self.up - forward * self.up.dot(forward)).normalized() else {
+            return Self::IDENTITY
+        };
+        Self::from_vectors(forward, up)
+    }
+
     /// Return the matrix inverted.
     #[must_use]
     pub const fn inverted(self) -> Matrix3x3 {
-        let determinant =
-            self.forward.x * self.left.y * self.up.z +
-            self.forward.y * self.left.z * self.up.x +
-            self.forward.z * self.left.x * self.up.y -
-            self.forward.x * self.left.z * self.up.y -
-            self.forward.y * self.left.x * self.up.z -
-            self.forward.z * self.left.y * self.up.x;
-
-        let determinant_inverse = 1.0 / determinant;
-
-        // SAFETY: Can be safely represented as this
-        let array: [[f32; 3]; 3] = unsafe { transmute(self) };
+        let determinant_inverse = 1.0 / self.determinant();
+
+        let array = self.as_array();
         let mut inverse = [[0.0f32; 3]; 3];
 
         // Do this funky loop because const does not allow for loops yet
@@ -172,7 +287,41 @@ impl Matrix3x3 {
             i += 1;
         }
 
-        unsafe { transmute(inverse) }
+        Self::from_array(inverse)
+    }
+
+    /// Convert the matrix to an array of rows in `forward`, `left`, `up` order.
+    #[must_use]
+    pub const fn as_array(self) -> [[f32; 3]; 3] {
+        [
+            [self.forward.x, self.forward.y, self.forward.z],
+            [self.left.x, self.left.y, self.left.z],
+            [self.up.x, self.up.y, self.up.z]
+        ]
+    }
+
+    /// Convert an array of rows in `forward`, `left`, `up` order to a matrix.
+    ///
+    /// This is the reverse of [`as_array`](Self::as_array).
+    #[must_use]
+    pub const fn from_array(array: [[f32; 3]; 3]) -> Matrix3x3 {
+        let [forward, left, up] = array;
+        Matrix3x3 {
+            forward: Vector3D { x: forward[0], y: forward[1], z: forward[2] },
+            left: Vector3D { x: left[0], y: left[1], z: left[2] },
+            up: Vector3D { x: up[0], y: up[1], z: up[2] }
+        }
+    }
+
+    /// Return true if each component is within `epsilon` of the corresponding component of `other`.
+    ///
+    /// This always returns false if either matrix has a NaN component.
+    #[must_use]
+    #[inline]
+    pub const fn approx_eq(self, other: Matrix3x3, epsilon: f32) -> bool {
+        self.forward.approx_eq(other.forward, epsilon)
+            && self.left.approx_eq(other.left, epsilon)
+            && self.up.approx_eq(other.up, epsilon)
     }
 }
 
@@ -194,10 +343,51 @@ pub struct Quaternion {
     pub w: f32,
 }
 
+const _: () = assert!(size_of::<Quaternion>() == 0x10);
+const _: () = assert!(offset_of!(Quaternion, vector) == 0x0 && offset_of!(Quaternion, w) == 0xC);
+
 impl Quaternion {
     /// Identity quaternion.
     pub const IDENTITY: Self = Self { vector: Vector3D::ZEROED, w: 1.0 };
 
+    /// Instantiate a quaternion that rotates `angle` around `axis`.
+    ///
+    /// The result converts to the same matrix as [`Matrix3x3::from_axis_angle`] with
+    /// [`as_matrix`](Self::as_matrix). The axis is normalized first; if it is close to zero,
+    /// [`Quaternion::IDENTITY`] is returned.
+    #[must_use]
+    pub fn from_axis_angle(axis: Vector3D, angle: Angle) -> Quaternion {
+        let Some(axis) = axis.normalized() else {
+            return Self::IDENTITY
+        };
+
+        // as_matrix() produces the transpose of the usual rotation matrix, so the vector is negated
+        let half_angle = angle.radians() * 0.5;
+        Quaternion {
+            vector: axis.scaled(-half_angle.fw_sin()),
+            w: half_angle.fw_cos()
+        }
+    }
+
+    /// Decompose the quaternion into a unit axis and an angle to rotate around it.
+    ///
+    /// This is the reverse of [`from_axis_angle`](Self::from_axis_angle). The quaternion is
+    /// normalized first, and the angle is between 0˚ and 180˚. If there is no rotation, the angle
+    /// is 0˚ and the axis is arbitrary.
+    #[must_use]
+    pub fn to_axis_angle(self) -> (Vector3D, Angle) {
+        let mut q = self.normalized();
+        if q.w < 0.0 {
+            q = -q;
+        }
+
+        // The vector is negated in from_axis_angle(), so negate it back
+        match q.vector.negated().normalized() {
+            Some(axis) => (axis, Angle::from_radians(q.w.clamp(-1.0, 1.0).fw_acos() * 2.0)),
+            None => (Vector3D { x: 1.0, y: 0.0, z: 0.0 }, Angle::_0_DEG)
+        }
+    }
+
     /// Square length of the quaternion.
     #[must_use]
     pub const fn square_length(self) -> f32 {
@@ -266,6 +456,30 @@ impl Quaternion {
         }
     }
 
+    /// Raise the quaternion to the power of `t`, scaling its rotation by `t`.
+    ///
+    /// The quaternion is normalized first, and the shortest rotation is used (i.e. `q` and `-q`
+    /// give the same result). Returns [`Quaternion::IDENTITY`] if there is no rotation.
+    #[must_use]
+    pub fn powf(self, t: f32) -> Quaternion {
+        let mut q = self.normalized();
+        if q.w < 0.0 {
+            q = -q;
+        }
+
+        let half_angle = q.w.clamp(-1.0, 1.0).fw_acos();
+        let sin_half_angle = half_angle.fw_sin();
+        if sin_half_angle.fw_is_close_to_zero() {
+            return Self::IDENTITY
+        }
+
+        let new_half_angle = half_angle * t;
+        Quaternion {
+            vector: q.vector.scaled(new_half_angle.fw_sin() / sin_half_angle),
+            w: new_half_angle.fw_cos()
+        }
+    }
+
     /// Interpolate this quaternion with another one by `by` amount, returning a normalized vector.
     ///
     /// This function is more accurate than [linear_interpolated_unnormalized](Self::linear_interpolated_unnormalized),
@@ -302,6 +516,64 @@ impl Quaternion {
         (with_n * r1 + a * r0).normalized()
     }
 
+    /// Average several quaternions, each with a weight.
+    ///
+    /// Each quaternion is normalized and flipped onto the same hemisphere as the most heavily
+    /// weighted quaternion, and then the weighted sum is normalized. Unlike repeatedly calling
+    /// [`interpolated`](Self::interpolated), the result does not depend on the order of the input.
+    ///
+    /// This is an approximation that is only accurate when the rotations are close to each other.
+    ///
+    /// Returns `None` if `quaternions` is empty or if the weights (or the weighted sum) add up to
+    /// zero.
+    #[must_use]
+    pub fn average(quaternions: &[(Quaternion, f32)]) -> Option<Quaternion> {
+        let total_weight: f32 = quaternions.iter().map(|(_, weight)| *weight).sum();
+        if total_weight.fw_is_close_to_zero() {
+            return None
+        }
+
+        let (reference, _) = quaternions.iter().max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        let reference = reference.normalized();
+
+        let mut sum = Quaternion { vector: Vector3D::ZEROED, w: 0.0 };
+        for &(quaternion, weight) in quaternions {
+            let mut quaternion = quaternion.normalized();
+            if quaternion.dot(reference) < 0.0 {
+                quaternion = -quaternion;
+            }
+            sum += quaternion * weight;
+        }
+
+        if sum.square_length().fw_is_close_to_zero() {
+            return None
+        }
+        Some(sum.normalized())
+    }
+
+    /// Get the angle of the rotation needed to go from this orientation to another one.
+    ///
+    /// Both quaternions are normalized first. The result is between 0˚ and 180˚.
+    #[must_use]
+    pub fn angle_to(self, other: Quaternion) -> Angle {
+        let cos_half_angle = self.normalized().dot(other.normalized()).fw_fabs().min(1.0);
+        Angle::from_radians(2.0 * cos_half_angle.fw_acos())
+    }
+
+    /// Rotate towards another orientation by at most `max_step`.
+    ///
+    /// If `target` is within `max_step`, `target` is returned (normalized). Otherwise, this is
+    /// [interpolated](Self::interpolated) towards `target` by exactly `max_step`.
+    #[must_use]
+    pub fn rotate_towards(self, target: Quaternion, max_step: Angle) -> Quaternion {
+        let angle = self.angle_to(target);
+        if angle <= max_step {
+            return target.normalized()
+        }
+
+        self.interpolated(target, max_step.radians() / angle.radians())
+    }
+
     /// Linear interpolate this quaternion with another one by `by` amount, returning a normalized
     /// vector.
     ///
@@ -342,6 +614,17 @@ impl Quaternion {
         self.dot(*self).fw_is_close_to(1.0)
     }
 
+    /// Return true if each component is within `epsilon` of the corresponding component of `other`.
+    ///
+    /// This always returns false if either quaternion has a NaN component.
+    ///
+    /// Note that `q` and `-q` represent the same rotation but are not considered equal.
+    #[must_use]
+    #[inline]
+    pub const fn approx_eq(self, other: Quaternion, epsilon: f32) -> bool {
+        self.vector.approx_eq(other.vector, epsilon) && (self.w - other.w).abs() <= epsilon
+    }
+
     const fn dot(self, with: Quaternion) -> f32 {
         let ww = self.w * with.w;
         self.vector.dot(with.vector) + ww
@@ -444,6 +727,9 @@ pub struct Vector2D {
     pub y: f32
 }
 
+const _: () = assert!(size_of::<Vector2D>() == 0x8);
+const _: () = assert!(offset_of!(Vector2D, x) == 0x0 && offset_of!(Vector2D, y) == 0x4);
+
 impl Vector2D {
     /// Vector with all components set to 0.
     pub const ZEROED: Self = Vector2D::from_scalar(0.0);
@@ -488,6 +774,22 @@ impl Vector2D {
         self.dot(self).fw_sqrt()
     }
 
+    /// Get the distance squared between this point and `other`.
+    ///
+    /// This is cheaper than calling [`distance_to`](Self::distance_to).
+    #[inline]
+    #[must_use]
+    pub fn distance_squared_to(self, other: Vector2D) -> f32 {
+        (self - other).magnitude_squared()
+    }
+
+    /// Get the distance between this point and `other`.
+    #[inline]
+    #[must_use]
+    pub fn distance_to(self, other: Vector2D) -> f32 {
+        (self - other).magnitude()
+    }
+
     /// Multiply all components with `amount`.
     #[inline]
     #[must_use]
@@ -508,17 +810,54 @@ impl Vector2D {
         }
     }
 
+    /// Return a vector with the smallest of each component from this and `other`.
+    #[inline]
+    #[must_use]
+    pub const fn component_min(self, other: Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y)
+        }
+    }
+
+    /// Return a vector with the largest of each component from this and `other`.
+    #[inline]
+    #[must_use]
+    pub const fn component_max(self, other: Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y)
+        }
+    }
+
+    /// Clamp each component between the corresponding components of `min` and `max`.
+    ///
+    /// Unlike [`f32::clamp`], this does not panic; if a component of `min` is greater than the
+    /// one in `max`, `max` takes precedence.
+    #[inline]
+    #[must_use]
+    pub const fn clamped(self, min: Self, max: Self) -> Self {
+        self.component_max(min).component_min(max)
+    }
+
     /// Convert the vector to a unit vector, if possible.
     #[inline]
     #[must_use]
     pub fn normalized(self) -> Option<Self> {
+        self.normalized_and_magnitude().map(|(normalized, _)| normalized)
+    }
+
+    /// Convert the vector to a unit vector, if possible, also returning its original magnitude.
+    #[inline]
+    #[must_use]
+    pub fn normalized_and_magnitude(self) -> Option<(Self, f32)> {
         let magnitude = self.magnitude();
         if magnitude.fw_is_close_to_zero(){
             None
         }
         else {
             // Bad for floating point precision, but needed to be accurate to the original...
-            Some(self.scaled(1.0 / magnitude))
+            Some((self.scaled(1.0 / magnitude), magnitude))
         }
     }
 
@@ -536,6 +875,20 @@ impl Vector2D {
         self + direction * offset
     }
 
+    /// Round to a [`Vector2DInt`], rounding ties to even.
+    ///
+    /// Returns `None` if either component is NaN or does not fit in an [`i16`] after rounding.
+    #[must_use]
+    pub fn to_vector2d_int_rounded(self) -> Option<Vector2DInt> {
+        if self.x.is_nan() || self.y.is_nan() {
+            return None
+        }
+        Some(Vector2DInt {
+            x: i16::try_from(self.x.fw_round_ties_even_to_int()).ok()?,
+            y: i16::try_from(self.y.fw_round_ties_even_to_int()).ok()?
+        })
+    }
+
     /// Compress to a 32-bit value.
     #[inline]
     #[must_use]
@@ -545,6 +898,15 @@ impl Vector2D {
             y: self.y.fw_compress_clamped()
         }
     }
+
+    /// Return true if each component is within `epsilon` of the corresponding component of `other`.
+    ///
+    /// This always returns false if either vector has a NaN component.
+    #[must_use]
+    #[inline]
+    pub const fn approx_eq(self, other: Vector2D, epsilon: f32) -> bool {
+        (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon
+    }
 }
 
 impl Add<Vector2D> for Vector2D {
@@ -593,6 +955,10 @@ impl MulAssign<f32> for Vector2D {
 }
 
 /// Represents a cuboid.
+///
+/// The axes are mapped like [`Matrix3x3`] (+X forward, +Y left, +Z up), so `back`/`front` are the
+/// minimum/maximum X, `right`/`left` are the minimum/maximum Y, and `bottom`/`top` are the
+/// minimum/maximum Z.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
 #[repr(C)]
@@ -606,6 +972,108 @@ pub struct Cube3D {
     pub back: f32
 }
 
+const _: () = assert!(size_of::<Cube3D>() == 0x18);
+const _: () = assert!(offset_of!(Cube3D, top) == 0x0 && offset_of!(Cube3D, left) == 0x4 && offset_of!(Cube3D, bottom) == 0x8 && offset_of!(Cube3D, right) == 0xC && offset_of!(Cube3D, front) == 0x10 && offset_of!(Cube3D, back) == 0x14);
+
+impl Cube3D {
+    /// A cuboid with inverted infinite bounds that contains nothing.
+    ///
+    /// Expanding or merging this with anything results in that thing, so this can be used as a
+    /// starting value for building bounds (see [`expanded_to_include`](Self::expanded_to_include)).
+    pub const EMPTY: Cube3D = Cube3D {
+        top: f32::NEG_INFINITY,
+        left: f32::NEG_INFINITY,
+        bottom: f32::INFINITY,
+        right: f32::INFINITY,
+        front: f32::NEG_INFINITY,
+        back: f32::INFINITY
+    };
+
+    /// Get the smallest cuboid containing both the cuboid and `point`.
+    #[must_use]
+    pub const fn expanded_to_include(self, point: Vector3D) -> Cube3D {
+        Cube3D {
+            top: self.top.max(point.z),
+            left: self.left.max(point.y),
+            bottom: self.bottom.min(point.z),
+            right: self.right.min(point.y),
+            front: self.front.max(point.x),
+            back: self.back.min(point.x)
+        }
+    }
+
+    /// Get the smallest cuboid containing both cuboids.
+    #[must_use]
+    pub const fn merged(self, other: Cube3D) -> Cube3D {
+        Cube3D {
+            top: self.top.max(other.top),
+            left: self.left.max(other.left),
+            bottom: self.bottom.min(other.bottom),
+            right: self.right.min(other.right),
+            front: self.front.max(other.front),
+            back: self.back.min(other.back)
+        }
+    }
+
+    /// Get the smallest cuboid containing all of the points.
+    ///
+    /// Returns `None` if there are no points.
+    #[must_use]
+    pub fn from_points<I: IntoIterator<Item = Vector3D>>(points: I) -> Option<Cube3D> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        let mut cube = Cube3D {
+            top: first.z,
+            left: first.y,
+            bottom: first.z,
+            right: first.y,
+            front: first.x,
+            back: first.x
+        };
+
+        for point in points {
+            cube.back = cube.back.min(point.x);
+            cube.front = cube.front.max(point.x);
+            cube.right = cube.right.min(point.y);
+            cube.left = cube.left.max(point.y);
+            cube.bottom = cube.bottom.min(point.z);
+            cube.top = cube.top.max(point.z);
+        }
+
+        Some(cube)
+    }
+
+    /// Get the signed distance from the point to the nearest face of the cuboid.
+    ///
+    /// The distance is negative if the point is inside, zero if it is on a face, and positive if it
+    /// is outside.
+    #[must_use]
+    pub fn signed_distance(self, point: Vector3D) -> f32 {
+        let center = Vector3D {
+            x: (self.back + self.front) * 0.5,
+            y: (self.right + self.left) * 0.5,
+            z: (self.bottom + self.top) * 0.5
+        };
+        let half_extents = Vector3D {
+            x: (self.front - self.back) * 0.5,
+            y: (self.left - self.right) * 0.5,
+            z: (self.top - self.bottom) * 0.5
+        };
+
+        let offset = point - center;
+        let q = Vector3D {
+            x: offset.x.fw_fabs() - half_extents.x,
+            y: offset.y.fw_fabs() - half_extents.y,
+            z: offset.z.fw_fabs() - half_extents.z
+        };
+
+        let outside = Vector3D { x: q.x.max(0.0), y: q.y.max(0.0), z: q.z.max(0.0) }.magnitude();
+        let inside = q.x.max(q.y).max(q.z).min(0.0);
+        outside + inside
+    }
+}
+
 /// Represents a cuboid, but not the same as [`Cube3D`] for some reason.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
@@ -620,6 +1088,9 @@ pub struct Rectangle3D {
     pub z_to: f32,
 }
 
+const _: () = assert!(size_of::<Rectangle3D>() == 0x18);
+const _: () = assert!(offset_of!(Rectangle3D, x_from) == 0x0 && offset_of!(Rectangle3D, x_to) == 0x4 && offset_of!(Rectangle3D, y_from) == 0x8 && offset_of!(Rectangle3D, y_to) == 0xC && offset_of!(Rectangle3D, z_from) == 0x10 && offset_of!(Rectangle3D, z_to) == 0x14);
+
 impl Rectangle3D {
     /// Return true if the point is inside the rectangle.
     #[inline]
@@ -643,6 +1114,97 @@ pub fn test_rotated_bounding_box(forward: Vector3D, up: Vector3D, position: Vect
     modified.x > 0.0 && modified.y > 0.0 && modified.z > 0.0 && modified.x < extent.x && modified.y < extent.y && modified.z < extent.z
 }
 
+/// Calculate the barycentric coordinates of `p` relative to the triangle `a`, `b`, `c`.
+///
+/// The returned weights correspond to `a`, `b`, and `c` respectively and sum to 1. All weights
+/// are within `0.0..=1.0` if `p` is inside the triangle.
+///
+/// Returns `None` if the triangle is degenerate (i.e. has zero area).
+#[must_use]
+pub fn barycentric(p: Vector2D, a: Vector2D, b: Vector2D, c: Vector2D) -> Option<(f32, f32, f32)> {
+    let area = (b - a).cross_product(c - a);
+    if area.fw_is_close_to_zero() {
+        return None
+    }
+
+    let weight_a = (b - p).cross_product(c - p) / area;
+    let weight_b = (c - p).cross_product(a - p) / area;
+    Some((weight_a, weight_b, 1.0 - weight_a - weight_b))
+}
+
+/// Calculate the signed area of the polygon formed by `points` using the shoelace formula.
+///
+/// The polygon is implicitly closed, so the last point connects back to the first. The area is
+/// positive if the points wind counterclockwise and negative if they wind clockwise. Polygons with
+/// fewer than three points have no area.
+#[must_use]
+pub const fn polygon_signed_area(points: &[Vector2D]) -> f32 {
+    let mut sum = 0.0;
+    let mut i = 0;
+    while i < points.len() {
+        let next = if i + 1 == points.len() { 0 } else { i + 1 };
+        sum += points[i].cross_product(points[next]);
+        i += 1;
+    }
+    sum * 0.5
+}
+
+/// Return true if `points` wind clockwise.
+///
+/// This is a convenience function for `polygon_signed_area(points) < 0.0`, so degenerate polygons
+/// are not considered clockwise.
+#[must_use]
+pub const fn polygon_is_clockwise(points: &[Vector2D]) -> bool {
+    polygon_signed_area(points) < 0.0
+}
+
+/// Calculate the average position of `points`.
+///
+/// This uses a running sum accumulated in [`f64`] to reduce precision loss for large point clouds.
+///
+/// Returns `None` if `points` is empty.
+#[must_use]
+pub const fn centroid(points: &[Vector3D]) -> Option<Vector3D> {
+    if points.is_empty() {
+        return None
+    }
+
+    let (mut x, mut y, mut z) = (0.0f64, 0.0f64, 0.0f64);
+    let mut i = 0;
+    while i < points.len() {
+        x += points[i].x as f64;
+        y += points[i].y as f64;
+        z += points[i].z as f64;
+        i += 1;
+    }
+
+    let count = points.len() as f64;
+    Some(Vector3D { x: (x / count) as f32, y: (y / count) as f32, z: (z / count) as f32 })
+}
+
+/// Calculate the average position of `points`.
+///
+/// Like [`centroid`], this uses a running sum accumulated in [`f64`].
+///
+/// Returns `None` if `points` is empty.
+#[must_use]
+pub const fn centroid_2d(points: &[Vector2D]) -> Option<Vector2D> {
+    if points.is_empty() {
+        return None
+    }
+
+    let (mut x, mut y) = (0.0f64, 0.0f64);
+    let mut i = 0;
+    while i < points.len() {
+        x += points[i].x as f64;
+        y += points[i].y as f64;
+        i += 1;
+    }
+
+    let count = points.len() as f64;
+    Some(Vector2D { x: (x / count) as f32, y: (y / count) as f32 })
+}
+
 /// Represents a vector with four components.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
@@ -655,6 +1217,23 @@ pub struct Vector4D {
     pub w: f32
 }
 
+const _: () = assert!(size_of::<Vector4D>() == 0x10);
+const _: () = assert!(offset_of!(Vector4D, x) == 0x0 && offset_of!(Vector4D, y) == 0x4 && offset_of!(Vector4D, z) == 0x8 && offset_of!(Vector4D, w) == 0xC);
+
+impl Vector4D {
+    /// Return true if each component is within `epsilon` of the corresponding component of `other`.
+    ///
+    /// This always returns false if either vector has a NaN component.
+    #[must_use]
+    #[inline]
+    pub const fn approx_eq(self, other: Vector4D, epsilon: f32) -> bool {
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+            && (self.w - other.w).abs() <= epsilon
+    }
+}
+
 /// Represents a projection matrix.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
@@ -667,26 +1246,92 @@ pub struct ProjectionMatrix {
     pub w: Vector4D
 }
 
-/// Represents a vector with three components.
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Copy, Clone, Debug, PartialEq)]
-#[repr(C)]
-#[expect(missing_docs)]
-pub struct Vector3D {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32
-}
+const _: () = assert!(size_of::<ProjectionMatrix>() == 0x40);
+const _: () = assert!(offset_of!(ProjectionMatrix, x) == 0x0 && offset_of!(ProjectionMatrix, y) == 0x10 && offset_of!(ProjectionMatrix, z) == 0x20 && offset_of!(ProjectionMatrix, w) == 0x30);
 
-impl Vector3D {
-    /// Vector with all components set to 0.
-    pub const ZEROED: Self = Vector3D::from_scalar(0.0);
+impl ProjectionMatrix {
+    /// Identity matrix.
+    pub const IDENTITY: ProjectionMatrix = ProjectionMatrix {
+        x: Vector4D { x: 1.0, y: 0.0, z: 0.0, w: 0.0 },
+        y: Vector4D { x: 0.0, y: 1.0, z: 0.0, w: 0.0 },
+        z: Vector4D { x: 0.0, y: 0.0, z: 1.0, w: 0.0 },
+        w: Vector4D { x: 0.0, y: 0.0, z: 0.0, w: 1.0 }
+    };
 
-    /// Return a Vector3D with x and y set to `scalar`.
-    #[inline]
+    /// Return `true` if all components of the matrix are finite.
+    ///
+    /// Matrices built from degenerate values (e.g. a zero aspect ratio or equal near and far planes)
+    /// will contain infinities or NaNs and return `false`.
     #[must_use]
-    pub const fn from_scalar(scalar: f32) -> Self {
-        Self { x: scalar, y: scalar, z: scalar }
+    pub fn is_valid(&self) -> bool {
+        [self.x, self.y, self.z, self.w]
+            .into_iter()
+            .all(|row| row.x.fw_is_finite() && row.y.fw_is_finite() && row.z.fw_is_finite() && row.w.fw_is_finite())
+    }
+
+    /// Transform the vector by the matrix.
+    ///
+    /// Vectors are treated as row vectors (i.e. `vector * matrix`), where each field of the matrix
+    /// is a row.
+    const fn transform(&self, vector: Vector4D) -> Vector4D {
+        Vector4D {
+            x: vector.x * self.x.x + vector.y * self.y.x + vector.z * self.z.x + vector.w * self.w.x,
+            y: vector.x * self.x.y + vector.y * self.y.y + vector.z * self.z.y + vector.w * self.w.y,
+            z: vector.x * self.x.z + vector.y * self.y.z + vector.z * self.z.z + vector.w * self.w.z,
+            w: vector.x * self.x.w + vector.y * self.y.w + vector.z * self.z.w + vector.w * self.w.w,
+        }
+    }
+
+    /// Project a point onto the viewport, returning its position in pixels.
+    ///
+    /// The point is transformed (with `w = 1.0`), divided by the resulting `w`, and then the
+    /// normalized device coordinates are mapped to `viewport`, where `(-1, 1)` is the top-left
+    /// corner and `(1, -1)` is the bottom-right corner. Pixel coordinates are floored.
+    ///
+    /// This assumes a Direct3D-style depth range where clip-space `z` goes from `0` at the near
+    /// plane to `w` at the far plane (rather than OpenGL's `-w` to `w`). Returns `None` if the
+    /// point is behind the near plane (i.e. `z < 0`) or behind the camera (i.e. `w <= 0`).
+    #[must_use]
+    pub fn project_point(self, point: Vector3D, viewport: Rectangle) -> Option<Vector2DInt> {
+        let clip = self.transform(Vector4D { x: point.x, y: point.y, z: point.z, w: 1.0 });
+        if clip.w <= 0.0 || clip.z < 0.0 {
+            return None
+        }
+
+        let ndc_x = clip.x / clip.w;
+        let ndc_y = clip.y / clip.w;
+
+        let x = (viewport.left as f32) + (ndc_x + 1.0) * 0.5 * (viewport.width() as f32);
+        let y = (viewport.top as f32) + (1.0 - ndc_y) * 0.5 * (viewport.height() as f32);
+
+        let to_pixel = |v: f32| v.fw_floor_to_int().clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        Some(Vector2DInt { x: to_pixel(x), y: to_pixel(y) })
+    }
+}
+
+/// Represents a vector with three components.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(C)]
+#[expect(missing_docs)]
+pub struct Vector3D {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32
+}
+
+const _: () = assert!(size_of::<Vector3D>() == 0xC);
+const _: () = assert!(offset_of!(Vector3D, x) == 0x0 && offset_of!(Vector3D, y) == 0x4 && offset_of!(Vector3D, z) == 0x8);
+
+impl Vector3D {
+    /// Vector with all components set to 0.
+    pub const ZEROED: Self = Vector3D::from_scalar(0.0);
+
+    /// Return a Vector3D with x and y set to `scalar`.
+    #[inline]
+    #[must_use]
+    pub const fn from_scalar(scalar: f32) -> Self {
+        Self { x: scalar, y: scalar, z: scalar }
     }
 
     /// Return `true` if all components of the vector are valid.
@@ -729,6 +1374,22 @@ impl Vector3D {
         self.magnitude_squared().fw_sqrt()
     }
 
+    /// Get the distance squared between this point and `other`.
+    ///
+    /// This is cheaper than calling [`distance_to`](Self::distance_to).
+    #[inline]
+    #[must_use]
+    pub fn distance_squared_to(self, other: Vector3D) -> f32 {
+        (self - other).magnitude_squared()
+    }
+
+    /// Get the distance between this point and `other`.
+    #[inline]
+    #[must_use]
+    pub fn distance_to(self, other: Vector3D) -> f32 {
+        (self - other).magnitude()
+    }
+
     /// Interpolate this vector with another one by `by` amount.
     #[must_use]
     pub fn linear_interpolated(self, with: Vector3D, by: f32) -> Vector3D {
@@ -738,16 +1399,78 @@ impl Vector3D {
         self * b + with * a
     }
 
+    /// Round each component to the nearest multiple of `step`.
+    ///
+    /// See [`fw_snap_to`](FloatOps::fw_snap_to).
+    #[must_use]
+    pub fn snapped(self, step: f32) -> Vector3D {
+        Vector3D {
+            x: self.x.fw_snap_to(step),
+            y: self.y.fw_snap_to(step),
+            z: self.z.fw_snap_to(step)
+        }
+    }
+
+    /// Spherically interpolate this direction with another one by `by` amount.
+    ///
+    /// Unlike [`linear_interpolated`](Self::linear_interpolated), this moves along the great circle
+    /// at a constant angular rate. Both vectors are expected to be normalized.
+    ///
+    /// If the vectors are nearly parallel or antiparallel, this falls back to
+    /// [`linear_interpolated`](Self::linear_interpolated).
+    #[must_use]
+    pub fn slerp(self, to: Vector3D, by: f32) -> Vector3D {
+        let by = by.clamp(0.0, 1.0);
+        let cos_theta = self.dot(to).clamp(-1.0, 1.0);
+        let theta = cos_theta.fw_acos();
+        let sin_theta = theta.fw_sin();
+
+        if sin_theta < 0.00001 {
+            return self.linear_interpolated(to, by)
+        }
+
+        let r0 = ((1.0 - by) * theta).fw_sin() / sin_theta;
+        let r1 = (by * theta).fw_sin() / sin_theta;
+        self * r0 + to * r1
+    }
+
     /// Convert the vector to a unit vector, if possible.
     #[must_use]
     pub fn normalized(self) -> Option<Self> {
+        self.normalized_and_magnitude().map(|(normalized, _)| normalized)
+    }
+
+    /// Convert the vector to a unit vector, if possible, also returning its original magnitude.
+    ///
+    /// This is useful for splitting a velocity into a direction and a speed.
+    #[must_use]
+    pub fn normalized_and_magnitude(self) -> Option<(Self, f32)> {
         let magnitude = self.magnitude();
         if magnitude.fw_is_close_to_zero() {
             None
         }
         else {
             // Bad for floating point precision, but needed to be accurate to the original...
-            Some(self.scaled(1.0 / magnitude))
+            Some((self.scaled(1.0 / magnitude), magnitude))
+        }
+    }
+
+    /// Convert the vector to a unit vector, if possible, using
+    /// [`fw_fast_inverse_sqrt`](FloatOps::fw_fast_inverse_sqrt).
+    ///
+    /// This is faster but less accurate than [`normalized`](Self::normalized), and the result may
+    /// be slightly off from unit length. Like [`normalized`](Self::normalized), vectors with a NaN
+    /// component result in a NaN vector rather than `None`.
+    #[must_use]
+    pub fn normalized_fast(self) -> Option<Self> {
+        let magnitude_squared = self.magnitude_squared();
+
+        // Same as normalized(), comparing the squared magnitude to avoid the square root
+        if magnitude_squared < 0.0001 * 0.0001 {
+            None
+        }
+        else {
+            Some(self.scaled(magnitude_squared.fw_fast_inverse_sqrt()))
         }
     }
 
@@ -762,6 +1485,38 @@ impl Vector3D {
         }
     }
 
+    /// Return a vector with the smallest of each component from this and `other`.
+    #[inline]
+    #[must_use]
+    pub const fn component_min(self, other: Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z)
+        }
+    }
+
+    /// Return a vector with the largest of each component from this and `other`.
+    #[inline]
+    #[must_use]
+    pub const fn component_max(self, other: Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z)
+        }
+    }
+
+    /// Clamp each component between the corresponding components of `min` and `max`.
+    ///
+    /// Unlike [`f32::clamp`], this does not panic; if a component of `min` is greater than the
+    /// one in `max`, `max` takes precedence.
+    #[inline]
+    #[must_use]
+    pub const fn clamped(self, min: Self, max: Self) -> Self {
+        self.component_max(min).component_min(max)
+    }
+
     /// Calculate the cross product with another vector (as 3D vectors) and return the Z coordinate.
     #[inline]
     #[must_use]
@@ -773,6 +1528,44 @@ impl Vector3D {
         }
     }
 
+    /// Reflect this vector off of a plane with the given normal.
+    ///
+    /// `normal` is expected to be normalized.
+    #[inline]
+    #[must_use]
+    pub const fn reflected(self, normal: Vector3D) -> Self {
+        let d = 2.0 * self.dot(normal);
+        Self {
+            x: self.x - normal.x * d,
+            y: self.y - normal.y * d,
+            z: self.z - normal.z * d
+        }
+    }
+
+    /// Get the component of this vector that is parallel to `onto`.
+    ///
+    /// `onto` is expected to be normalized.
+    #[inline]
+    #[must_use]
+    pub const fn projected_onto(self, onto: Vector3D) -> Self {
+        onto.scaled(self.dot(onto))
+    }
+
+    /// Get the component of this vector that is perpendicular to `onto`.
+    ///
+    /// This is the remainder of [`projected_onto`](Self::projected_onto), and `onto` is likewise
+    /// expected to be normalized.
+    #[inline]
+    #[must_use]
+    pub const fn rejected_from(self, onto: Vector3D) -> Self {
+        let projected = self.projected_onto(onto);
+        Self {
+            x: self.x - projected.x,
+            y: self.y - projected.y,
+            z: self.z - projected.z
+        }
+    }
+
     /// Return the value represented as a [`Euler2D`].
     #[inline]
     #[must_use]
@@ -871,6 +1664,69 @@ impl Vector3D {
     pub const fn component_is_positive(&self, component: Vector3DComponent) -> bool {
         self.get_component(component) > 0.0
     }
+
+    /// Compare this vector with another vector, giving a total ordering.
+    ///
+    /// Components are compared in order (x, then y, then z) using [`f32::total_cmp`]. As such,
+    /// `-0.0` is ordered before `0.0`, positive NaNs are ordered after everything else, and
+    /// negative NaNs are ordered before everything else.
+    #[must_use]
+    pub fn total_cmp(self, other: Vector3D) -> Ordering {
+        self.x.total_cmp(&other.x)
+            .then_with(|| self.y.total_cmp(&other.y))
+            .then_with(|| self.z.total_cmp(&other.z))
+    }
+
+    /// Compare this vector with another vector, giving a total ordering.
+    ///
+    /// This is the same as [`total_cmp`](Self::total_cmp), but takes references so it can be
+    /// passed directly to functions like [`slice::sort_by`].
+    #[must_use]
+    #[inline]
+    pub fn total_cmp_lexicographic(&self, other: &Vector3D) -> Ordering {
+        self.total_cmp(*other)
+    }
+
+    /// Return whether each component is less than the corresponding component of `other`.
+    #[must_use]
+    #[inline]
+    pub const fn cmp_lt(self, other: Vector3D) -> (bool, bool, bool) {
+        (self.x < other.x, self.y < other.y, self.z < other.z)
+    }
+
+    /// Return whether each component is greater than the corresponding component of `other`.
+    #[must_use]
+    #[inline]
+    pub const fn cmp_gt(self, other: Vector3D) -> (bool, bool, bool) {
+        (self.x > other.x, self.y > other.y, self.z > other.z)
+    }
+
+    /// Return whether each component is less than or equal to the corresponding component of
+    /// `other`.
+    #[must_use]
+    #[inline]
+    pub const fn cmp_le(self, other: Vector3D) -> (bool, bool, bool) {
+        (self.x <= other.x, self.y <= other.y, self.z <= other.z)
+    }
+
+    /// Return whether each component is greater than or equal to the corresponding component of
+    /// `other`.
+    #[must_use]
+    #[inline]
+    pub const fn cmp_ge(self, other: Vector3D) -> (bool, bool, bool) {
+        (self.x >= other.x, self.y >= other.y, self.z >= other.z)
+    }
+
+    /// Return true if each component is within `epsilon` of the corresponding component of `other`.
+    ///
+    /// This always returns false if either vector has a NaN component.
+    #[must_use]
+    #[inline]
+    pub const fn approx_eq(self, other: Vector3D, epsilon: f32) -> bool {
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+    }
 }
 
 /// Projection derived from [`Vector3D::projection`]
@@ -951,6 +1807,8 @@ impl Display for Vector3D {
 }
 
 /// Represents a two-component vector using 16-bit ints (i.e. an X and Y coordinate in pixels).
+///
+/// Adding and subtracting wraps on overflow.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
 #[repr(C)]
@@ -960,6 +1818,46 @@ pub struct Vector2DInt {
     pub y: i16
 }
 
+const _: () = assert!(size_of::<Vector2DInt>() == 0x4);
+const _: () = assert!(offset_of!(Vector2DInt, x) == 0x0 && offset_of!(Vector2DInt, y) == 0x2);
+
+impl Vector2DInt {
+    /// Convert to a [`Vector2D`].
+    ///
+    /// This is always exact.
+    #[inline]
+    #[must_use]
+    pub const fn as_vector2d(self) -> Vector2D {
+        Vector2D { x: self.x as f32, y: self.y as f32 }
+    }
+}
+
+impl Add<Vector2DInt> for Vector2DInt {
+    type Output = Self;
+    fn add(self, value: Vector2DInt) -> Self {
+        Self {
+            x: self.x.wrapping_add(value.x),
+            y: self.y.wrapping_add(value.y)
+        }
+    }
+}
+
+impl Sub<Vector2DInt> for Vector2DInt {
+    type Output = Self;
+    fn sub(self, value: Vector2DInt) -> Self {
+        Self {
+            x: self.x.wrapping_sub(value.x),
+            y: self.y.wrapping_sub(value.y)
+        }
+    }
+}
+
+impl From<Vector2DInt> for Vector2D {
+    fn from(value: Vector2DInt) -> Self {
+        value.as_vector2d()
+    }
+}
+
 /// Represents a rotation using an Euler angle, besides roll.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
@@ -970,6 +1868,9 @@ pub struct Euler2D {
     pub pitch: Angle
 }
 
+const _: () = assert!(size_of::<Euler2D>() == 0x8);
+const _: () = assert!(offset_of!(Euler2D, yaw) == 0x0 && offset_of!(Euler2D, pitch) == 0x4);
+
 impl Euler2D {
     /// Convert the Euler2D into a 3D vector.
     #[inline]
@@ -986,6 +1887,40 @@ impl Euler2D {
             z: sine_pitch
         }
     }
+
+    /// Convert the Euler2D into a 3D vector using the given up axis.
+    ///
+    /// The axes of [`as_vector`](Self::as_vector) (which is +Z up) are rotated so that the result
+    /// stays right-handed:
+    /// - [`Vector3DComponent::Z`]: `(x, y, z)` (same as [`as_vector`](Self::as_vector))
+    /// - [`Vector3DComponent::Y`]: `(y, z, x)`, so zero yaw faces +Z and 90˚ yaw faces +X
+    /// - [`Vector3DComponent::X`]: `(z, x, y)`, so zero yaw faces +Y and 90˚ yaw faces +Z
+    #[must_use]
+    pub fn as_vector_with_up(self, up: Vector3DComponent) -> Vector3D {
+        let Vector3D { x, y, z } = self.as_vector();
+        match up {
+            Vector3DComponent::X => Vector3D { x: z, y: x, z: y },
+            Vector3DComponent::Y => Vector3D { x: y, y: z, z: x },
+            Vector3DComponent::Z => Vector3D { x, y, z }
+        }
+    }
+
+    /// Interpolate this rotation with another one by `by` amount.
+    ///
+    /// Yaw takes the shortest path (so going from 170˚ to -170˚ crosses 180˚) and is wrapped to
+    /// `[-π, π)`. Pitch is interpolated linearly and clamped to `[-π/2, π/2]`.
+    #[must_use]
+    pub fn interpolated(self, with: Euler2D, by: f32) -> Euler2D {
+        let by = by.clamp(0.0, 1.0);
+
+        let yaw_delta = (with.yaw - self.yaw).wrapped_signed();
+        let yaw = (self.yaw + yaw_delta * by).wrapped_signed();
+
+        let pitch = self.pitch.radians() * (1.0 - by) + with.pitch.radians() * by;
+        let pitch = Angle::from_radians(pitch.clamp(-f32::FW_HALF_PI, f32::FW_HALF_PI));
+
+        Euler2D { yaw, pitch }
+    }
 }
 
 impl From<Euler2D> for Vector3D {
@@ -1013,6 +1948,9 @@ pub struct Euler3D {
     pub roll: Angle
 }
 
+const _: () = assert!(size_of::<Euler3D>() == 0xC);
+const _: () = assert!(offset_of!(Euler3D, yaw) == 0x0 && offset_of!(Euler3D, pitch) == 0x4 && offset_of!(Euler3D, roll) == 0x8);
+
 impl Euler3D {
     /// Convert to a matrix.
     #[must_use]
@@ -1069,6 +2007,10 @@ pub struct Plane2D {
     pub offset: f32,
     pub vector: Vector2D
 }
+
+const _: () = assert!(size_of::<Plane2D>() == 0xC);
+const _: () = assert!(offset_of!(Plane2D, offset) == 0x0 && offset_of!(Plane2D, vector) == 0x4);
+
 impl Plane2D {
     /// Get the distance `point` is from this plane.
     #[must_use]
@@ -1076,6 +2018,26 @@ impl Plane2D {
     pub const fn distance_to_point(self, point: Vector2D) -> f32 {
         point.dot(self.vector) - self.offset
     }
+
+    /// Get which side of this plane `point` is on, using the sign of
+    /// [`distance_to_point`](Self::distance_to_point).
+    ///
+    /// Returns [`Ordering::Greater`] if it is in front, [`Ordering::Less`] if it is behind, and
+    /// [`Ordering::Equal`] if it is exactly on the plane or the distance is NaN.
+    #[must_use]
+    #[inline]
+    pub const fn classify(self, point: Vector2D) -> Ordering {
+        let distance = self.distance_to_point(point);
+        if distance > 0.0 {
+            Ordering::Greater
+        }
+        else if distance < 0.0 {
+            Ordering::Less
+        }
+        else {
+            Ordering::Equal
+        }
+    }
 }
 
 /// Represents a 3D plane.
@@ -1087,13 +2049,127 @@ pub struct Plane3D {
     pub vector: Vector3D,
     pub offset: f32,
 }
+
+const _: () = assert!(size_of::<Plane3D>() == 0x10);
+const _: () = assert!(offset_of!(Plane3D, vector) == 0x0 && offset_of!(Plane3D, offset) == 0xC);
+
 impl Plane3D {
+    /// Calculate the plane a triangle lies on.
+    ///
+    /// The normal is `(b - a) × (c - a)`, normalized, so the front side is the side the triangle
+    /// appears counterclockwise from, and [`distance_to_point`](Self::distance_to_point) is
+    /// positive on that side.
+    ///
+    /// Returns `None` if the triangle is degenerate (i.e. the points are colinear).
+    #[must_use]
+    pub fn from_points(a: Vector3D, b: Vector3D, c: Vector3D) -> Option<Plane3D> {
+        let cross = (b - a).cross_product(c - a);
+        let magnitude = cross.magnitude();
+        if magnitude.fw_is_close_to_zero() {
+            return None
+        }
+
+        let vector = cross.scaled(1.0 / magnitude);
+        Some(Plane3D { vector, offset: vector.dot(a) })
+    }
+
     /// Get the distance `point` is from this plane.
     #[must_use]
     #[inline]
     pub const fn distance_to_point(self, point: Vector3D) -> f32 {
         point.dot(self.vector) - self.offset
     }
+
+    /// Scale the plane so its normal is a unit vector.
+    ///
+    /// This makes [`distance_to_point`](Self::distance_to_point) return the true distance. Returns
+    /// `None` if the normal is close to zero.
+    #[must_use]
+    pub fn normalized(self) -> Option<Plane3D> {
+        let (vector, magnitude) = self.vector.normalized_and_magnitude()?;
+        Some(Plane3D {
+            vector,
+            offset: self.offset * (1.0 / magnitude)
+        })
+    }
+
+    /// Return true if both planes lie in the same place and face the same direction.
+    ///
+    /// All components of the vectors and the offsets must be [close](FloatOps::fw_is_close_to).
+    /// Planes facing opposite directions are not considered coincident.
+    #[must_use]
+    pub fn is_coincident(self, other: Plane3D) -> bool {
+        self.offset.fw_is_close_to(other.offset)
+            && self.vector.x.fw_is_close_to(other.vector.x)
+            && self.vector.y.fw_is_close_to(other.vector.y)
+            && self.vector.z.fw_is_close_to(other.vector.z)
+    }
+
+    /// Classify a triangle against the plane.
+    ///
+    /// Vertices [closer](FloatOps::fw_is_closer_to) than 0.0001 to the plane are considered to lie
+    /// on it. A triangle is only split if it has vertices on both sides of the plane, so a triangle
+    /// with vertices on the plane and the rest on one side is entirely on that side.
+    #[must_use]
+    pub fn split_triangle(self, a: Vector3D, b: Vector3D, c: Vector3D) -> TriangleSplit {
+        let vertices = [a, b, c];
+        let distances = vertices.map(|v| {
+            let distance = self.distance_to_point(v);
+            if distance.fw_is_closer_to(0.0) { 0.0 } else { distance }
+        });
+
+        let in_front = distances.iter().any(|&d| d > 0.0);
+        let behind = distances.iter().any(|&d| d < 0.0);
+        match (in_front, behind) {
+            (false, false) => return TriangleSplit::Coplanar,
+            (true, false) => return TriangleSplit::Front,
+            (false, true) => return TriangleSplit::Back,
+            (true, true) => ()
+        }
+
+        // Exactly one vertex is in front and one is behind, so there are always two points: either
+        // two edge crossings, or the vertex on the plane and the crossing of the opposite edge.
+        let mut intersections = [Vector3D::ZEROED; 2];
+        let mut count = 0;
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+            if distances[i] == 0.0 {
+                intersections[count] = vertices[i];
+                count += 1;
+            }
+            else if distances[j] != 0.0 && (distances[i] > 0.0) != (distances[j] > 0.0) {
+                let t = distances[i] / (distances[i] - distances[j]);
+                intersections[count] = vertices[i] + (vertices[j] - vertices[i]) * t;
+                count += 1;
+            }
+        }
+        debug_assert_eq!(count, 2);
+
+        TriangleSplit::Split { intersections }
+    }
+}
+
+/// Describes where a triangle lies relative to a plane.
+///
+/// Returned by [`Plane3D::split_triangle`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TriangleSplit {
+    /// The triangle is in front of the plane, possibly with some vertices on it.
+    Front,
+
+    /// The triangle is behind the plane, possibly with some vertices on it.
+    Back,
+
+    /// All vertices lie on the plane.
+    Coplanar,
+
+    /// The plane passes through the triangle.
+    Split {
+        /// The two points where the plane crosses the triangle's edges, in winding order.
+        ///
+        /// If a vertex lies on the plane, it is one of these points.
+        intersections: [Vector3D; 2]
+    }
 }
 
 /// Angle value.
@@ -1104,6 +2180,8 @@ impl Plane3D {
 #[repr(transparent)]
 pub struct Angle(pub f32);
 
+const _: () = assert!(size_of::<Angle>() == 0x4);
+
 impl Angle {
     /// The default horizontal FoV in degrees (70 degrees) for the game.
     pub const DEFAULT_HORIZONTAL_FOV: Angle = Angle::from_degrees(70.0);
@@ -1181,44 +2259,158 @@ impl Angle {
     pub const fn radians(self) -> f32 {
         self.0
     }
-}
-
-impl TrigScalarFloatOps for Angle {
-    type Scalar = f32;
 
+    /// Instantiate an angle from the given turns, where 1.0 is a full circle (360˚).
+    #[must_use]
     #[inline]
-    fn tfw_sin(self) -> Self::Scalar {
-        self.radians().tfw_sin()
+    pub const fn from_turns(turns: f32) -> Self {
+        Self::from_radians(turns * f32::FW_2PI)
     }
 
+    /// Get the value as turns, where 1.0 is a full circle (360˚).
+    #[must_use]
     #[inline]
-    fn tfw_cos(self) -> Self::Scalar {
-        self.radians().tfw_cos()
+    pub const fn turns(self) -> f32 {
+        self.0 / f32::FW_2PI
     }
 
+    /// Calculate the sine of the angle.
+    #[must_use]
     #[inline]
-    fn tfw_tan(self) -> Self::Scalar {
-        self.radians().tfw_tan()
+    pub fn sin(self) -> f32 {
+        self.radians().fw_sin()
     }
-}
 
-impl Display for Angle {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.write_fmt(format_args!("{}{}", self.degrees(), UTF8_DEGREES))
-    }
-}
-impl Debug for Angle {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.write_fmt(format_args!("Angle({})", self.0))
+    /// Calculate the cosine of the angle.
+    #[must_use]
+    #[inline]
+    pub fn cos(self) -> f32 {
+        self.radians().fw_cos()
     }
-}
 
-impl Neg for Angle {
-    type Output = Angle;
-    fn neg(self) -> Self::Output {
-        Self(-self.0)
+    /// Calculate the tangent of the angle.
+    #[must_use]
+    #[inline]
+    pub fn tan(self) -> f32 {
+        self.radians().fw_tan()
     }
-}
+
+    /// Calculate the sine and cosine of the angle, in that order.
+    #[must_use]
+    #[inline]
+    pub fn sin_cos(self) -> (f32, f32) {
+        (self.sin(), self.cos())
+    }
+
+    /// Instantiate an angle from a signed fixed-point fraction of a turn.
+    ///
+    /// `bits` is the number of fractional bits, so the angle is `value / 2^bits` turns. For
+    /// example, with 8 bits, `64` is a quarter turn (90˚) and `-128` is a half turn (-180˚).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits >= 64`.
+    #[must_use]
+    pub fn from_fixed(value: i16, bits: u32) -> Angle {
+        Self::from_radians((value as f32) / Self::fixed_scale(bits) * f32::FW_2PI)
+    }
+
+    /// Convert the angle to a signed fixed-point fraction of a turn.
+    ///
+    /// This is the inverse of [`from_fixed`](Self::from_fixed). The value is rounded to the
+    /// nearest step (ties to even) and saturated to the range of an [`i16`]; angles are not
+    /// wrapped, so angles outside of what `bits` can represent will saturate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits >= 64`.
+    #[must_use]
+    pub fn to_fixed(self, bits: u32) -> i16 {
+        let value = (self.0 / f32::FW_2PI * Self::fixed_scale(bits)).fw_round_ties_even_to_int();
+        value.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+    }
+
+    fn fixed_scale(bits: u32) -> f32 {
+        assert!(bits < 64, "bits must be less than 64");
+        (1u64 << bits) as f32
+    }
+
+    /// Wrap the angle to `[-π, π)`.
+    fn wrapped_signed(self) -> Angle {
+        let turns = ((self.0 + f32::FW_PI) / f32::FW_2PI).fw_floor();
+        Self(self.0 - turns * f32::FW_2PI)
+    }
+}
+
+impl TrigScalarFloatOps for Angle {
+    type Scalar = f32;
+
+    #[inline]
+    fn tfw_sin(self) -> Self::Scalar {
+        self.radians().tfw_sin()
+    }
+
+    #[inline]
+    fn tfw_cos(self) -> Self::Scalar {
+        self.radians().tfw_cos()
+    }
+
+    #[inline]
+    fn tfw_tan(self) -> Self::Scalar {
+        self.radians().tfw_tan()
+    }
+}
+
+impl Display for Angle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("{}{}", self.degrees(), UTF8_DEGREES))
+    }
+}
+impl Debug for Angle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("Angle({})", self.0))
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Add<Angle> for Angle {
+    type Output = Angle;
+    fn add(self, rhs: Angle) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub<Angle> for Angle {
+    type Output = Angle;
+    fn sub(self, rhs: Angle) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Mul<f32> for Angle {
+    type Output = Angle;
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl AddAssign<Angle> for Angle {
+    fn add_assign(&mut self, value: Self) {
+        *self = *self + value;
+    }
+}
+
+impl SubAssign<Angle> for Angle {
+    fn sub_assign(&mut self, value: Self) {
+        *self = *self - value;
+    }
+}
 
 impl PartialOrd for Angle {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -1232,6 +2424,8 @@ impl PartialOrd for Angle {
 #[repr(transparent)]
 pub struct CompressedFloat(pub i16);
 
+const _: () = assert!(size_of::<CompressedFloat>() == 0x2);
+
 impl CompressedFloat {
     /// Decompress back into a float.
     #[must_use]
@@ -1250,6 +2444,9 @@ pub struct CompressedVector2D {
     pub y: CompressedFloat
 }
 
+const _: () = assert!(size_of::<CompressedVector2D>() == 0x4);
+const _: () = assert!(offset_of!(CompressedVector2D, x) == 0x0 && offset_of!(CompressedVector2D, y) == 0x2);
+
 impl CompressedVector2D {
     /// Decompress the vector.
     #[must_use]
@@ -1270,6 +2467,8 @@ impl CompressedVector2D {
 #[repr(transparent)]
 pub struct CompressedVector3D(pub i32);
 
+const _: () = assert!(size_of::<CompressedVector3D>() == 0x4);
+
 impl CompressedVector3D {
     /// Decompress the vector.
     #[must_use]
@@ -1289,6 +2488,24 @@ impl CompressedVector3D {
     }
 }
 
+/// Decompress a buffer of compressed vectors (such as vertex normals) into `dst`.
+///
+/// This is equivalent to calling [`CompressedVector3D::decompress`] on each element.
+///
+/// Returns `None` (without writing anything) if `src` and `dst` have different lengths.
+#[must_use]
+pub fn decode_compressed_normals(src: &[CompressedVector3D], dst: &mut [Vector3D]) -> Option<()> {
+    if src.len() != dst.len() {
+        return None
+    }
+
+    for (compressed, decompressed) in src.iter().zip(dst.iter_mut()) {
+        *decompressed = compressed.decompress();
+    }
+
+    Some(())
+}
+
 /// Matrix3x3 with position and scale component.
 ///
 /// Represents a basic 3D transformation.
@@ -1303,13 +2520,26 @@ pub struct Matrix4x3 {
 }
 
 impl Matrix4x3 {
-    /// A [`Matrix4x3`] with a scale of 1 and no position or scale.
+    /// A [`Matrix4x3`] with a scale of 1 and no position or rotation.
     pub const IDENTITY: Matrix4x3 = Matrix4x3 {
         scale: 1.0,
         position: Vector3D::ZEROED,
         rotation: Matrix3x3::IDENTITY
     };
 
+    /// Return true if the matrix is [close](FloatOps::fw_is_close_to) to [`Matrix4x3::IDENTITY`].
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        let close = |a: Vector3D, b: Vector3D| a.x.fw_is_close_to(b.x) && a.y.fw_is_close_to(b.y) && a.z.fw_is_close_to(b.z);
+        let identity = Self::IDENTITY;
+
+        self.scale.fw_is_close_to(identity.scale)
+            && close(self.position, identity.position)
+            && close(self.rotation.forward, identity.rotation.forward)
+            && close(self.rotation.left, identity.rotation.left)
+            && close(self.rotation.up, identity.rotation.up)
+    }
+
     /// Instantiate using a [`Matrix3x3`], setting `scale` to 1.0 and `position` to [`Vector3D::ZEROED`]
     #[must_use]
     pub const fn from_matrix3x3(matrix3x3: Matrix3x3) -> Self {
@@ -1363,6 +2593,20 @@ impl Matrix4x3 {
         self.transform_vector(point) + self.position
     }
 
+    /// Transform each point in `input`, writing the results to `output`.
+    ///
+    /// This is equivalent to calling [`transform_point`](Self::transform_point) on each point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` and `output` are not the same length.
+    pub fn transform_points(&self, input: &[Vector3D], output: &mut [Vector3D]) {
+        assert_eq!(input.len(), output.len(), "input and output must be the same length");
+        for (point, result) in input.iter().zip(output.iter_mut()) {
+            *result = self.transform_point(*point);
+        }
+    }
+
     /// Transform the point, un-applying transform, scale, and then rotation.
     #[must_use]
     pub fn inverse_transform_point(&self, point: Vector3D) -> Vector3D {
@@ -1473,12 +2717,96 @@ impl From<Matrix3x3> for Matrix4x3 {
 }
 
 const _: () = assert!(size_of::<Matrix4x3>() == 0x34);
+const _: () = assert!(offset_of!(Matrix4x3, scale) == 0x0 && offset_of!(Matrix4x3, rotation) == 0x4 && offset_of!(Matrix4x3, position) == 0x28);
+
+/// Compact serde representation of a [`Matrix4x3`].
+///
+/// By default, [`Matrix4x3`] serializes its full rotation matrix. This instead stores the rotation
+/// as a [`Quaternion`], which is far easier to edit by hand, and it can be used with
+/// `#[serde(with = "funnel_web::vector::matrix4x3_compact")]`.
+///
+/// The rotation is converted with [`Matrix3x3::as_quaternion`] and [`Quaternion::as_matrix`], so
+/// it is expected to be a rotation matrix, and it may not round-trip exactly due to rounding.
+#[cfg(feature = "serde")]
+pub mod matrix4x3_compact {
+    use super::{Matrix4x3, Quaternion, Vector3D};
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct CompactMatrix4x3 {
+        scale: f32,
+        rotation: Quaternion,
+        position: Vector3D
+    }
+
+    /// Serialize a [`Matrix4x3`] in the compact form.
+    pub fn serialize<S: serde::Serializer>(matrix: &Matrix4x3, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&CompactMatrix4x3 {
+            scale: matrix.scale,
+            rotation: matrix.rotation.as_quaternion(),
+            position: matrix.position
+        }, serializer)
+    }
+
+    /// Deserialize a [`Matrix4x3`] from the compact form.
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Matrix4x3, D::Error> {
+        let compact = <CompactMatrix4x3 as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(Matrix4x3 {
+            scale: compact.scale,
+            rotation: compact.rotation.as_matrix(),
+            position: compact.position
+        })
+    }
+}
 
 #[cfg(test)]
 mod test {
+    use core::cmp::Ordering;
+    use crate::float::FloatOps;
     use crate::nudge::fix_decimal_rounding;
     use crate::util::assert_similar;
-    use crate::vector::Angle;
+    use crate::rectangle::Rectangle;
+    use crate::vector::{barycentric, centroid, centroid_2d, decode_compressed_normals, polygon_is_clockwise, polygon_signed_area, Angle, CompressedVector3D, Cube3D, Euler2D, Euler3D, Matrix2x3, Matrix3x3, Matrix4x3, Plane2D, Plane3D, ProjectionMatrix, Quaternion, TriangleSplit, Vector2D, Vector2DInt, Vector3D, Vector3DComponent, Vector4D};
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn matrix4x3_compact_serde() {
+        use alloc::vec;
+        use serde_test::{assert_tokens, Token};
+        use crate::vector::Matrix4x3;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Transform(#[serde(with = "crate::vector::matrix4x3_compact")] Matrix4x3);
+
+        let matrix = Matrix4x3 {
+            scale: 2.0,
+            rotation: Quaternion { vector: Vector3D { x: 0.0, y: 0.0, z: 1.0 }, w: 0.0 }.as_matrix(),
+            position: Vector3D { x: 1.0, y: 2.0, z: 3.0 }
+        };
+        assert_eq!(matrix.rotation.forward, Vector3D { x: -1.0, y: 0.0, z: 0.0 });
+
+        let vector = |x, y, z| [
+            Token::Struct { name: "Vector3D", len: 3 },
+            Token::Str("x"), Token::F32(x),
+            Token::Str("y"), Token::F32(y),
+            Token::Str("z"), Token::F32(z),
+            Token::StructEnd
+        ];
+
+        let mut tokens = vec![
+            Token::NewtypeStruct { name: "Transform" },
+            Token::Struct { name: "CompactMatrix4x3", len: 3 },
+            Token::Str("scale"), Token::F32(2.0),
+            Token::Str("rotation"),
+            Token::Struct { name: "Quaternion", len: 2 },
+            Token::Str("vector")
+        ];
+        tokens.extend(vector(0.0, 0.0, 1.0));
+        tokens.extend([Token::Str("w"), Token::F32(0.0), Token::StructEnd, Token::Str("position")]);
+        tokens.extend(vector(1.0, 2.0, 3.0));
+        tokens.push(Token::StructEnd);
+
+        assert_tokens(&Transform(matrix), &tokens);
+    }
 
     #[test]
     fn check_angle_constants() {
@@ -1507,4 +2835,966 @@ mod test {
             assert_eq!(fix_decimal_rounding(degrees_calculated), deg as f32, "{deg}˚ did not nudge");
         }
     }
+
+    /// Basic perspective projection looking down +Z.
+    fn perspective(near: f32, far: f32) -> ProjectionMatrix {
+        let q = far / (far - near);
+        ProjectionMatrix {
+            x: Vector4D { x: 1.0, y: 0.0, z: 0.0, w: 0.0 },
+            y: Vector4D { x: 0.0, y: 1.0, z: 0.0, w: 0.0 },
+            z: Vector4D { x: 0.0, y: 0.0, z: q, w: 1.0 },
+            w: Vector4D { x: 0.0, y: 0.0, z: -q * near, w: 0.0 },
+        }
+    }
+
+    #[test]
+    fn project_point_on_view_axis() {
+        let projection = perspective(1.0, 100.0);
+        let viewport = Rectangle::from_width_and_height(640, 480);
+
+        assert_eq!(projection.project_point(Vector3D { x: 0.0, y: 0.0, z: 10.0 }, viewport), Some(Vector2DInt { x: 320, y: 240 }));
+        assert_eq!(projection.project_point(Vector3D { x: 0.0, y: 0.0, z: -10.0 }, viewport), None);
+        assert_eq!(projection.project_point(Vector3D { x: 0.0, y: 0.0, z: 0.5 }, viewport), None);
+    }
+
+    #[test]
+    fn projection_matrix_validity() {
+        assert!(ProjectionMatrix::IDENTITY.is_valid());
+        assert!(perspective(1.0, 100.0).is_valid());
+        assert!(!perspective(10.0, 10.0).is_valid());
+        assert_eq!(ProjectionMatrix::IDENTITY.transform(Vector4D { x: 1.0, y: 2.0, z: 3.0, w: 4.0 }), Vector4D { x: 1.0, y: 2.0, z: 3.0, w: 4.0 });
+    }
+
+    #[test]
+    fn cube_signed_distance() {
+        let cube = Cube3D { top: 3.0, left: 2.0, bottom: -3.0, right: -2.0, front: 1.0, back: -1.0 };
+
+        assert_eq!(cube.signed_distance(Vector3D::ZEROED), -1.0);
+        assert_eq!(cube.signed_distance(Vector3D { x: 0.5, y: 0.0, z: 2.75 }), -0.25);
+
+        assert_eq!(cube.signed_distance(Vector3D { x: 1.0, y: 0.0, z: 0.0 }), 0.0);
+        assert_eq!(cube.signed_distance(Vector3D { x: 0.0, y: -2.0, z: 1.0 }), 0.0);
+        assert_eq!(cube.signed_distance(Vector3D { x: 1.0, y: 2.0, z: 3.0 }), 0.0);
+
+        assert_eq!(cube.signed_distance(Vector3D { x: 0.0, y: 0.0, z: 5.0 }), 2.0);
+        assert_eq!(cube.signed_distance(Vector3D { x: 4.0, y: 6.0, z: 0.0 }), 5.0);
+    }
+
+    #[test]
+    fn cube_bounds_building() {
+        let v = |x, y, z| Vector3D { x, y, z };
+        let points = [v(1.0, -2.0, 3.0), v(-4.0, 5.0, 0.5), v(0.0, 0.0, -6.0), v(2.5, 1.0, 1.0)];
+        let expected = Cube3D { top: 3.0, left: 5.0, bottom: -6.0, right: -2.0, front: 2.5, back: -4.0 };
+
+        let folded = points.iter().fold(Cube3D::EMPTY, |cube, &p| cube.expanded_to_include(p));
+        assert_eq!(folded, expected);
+        assert_eq!(Cube3D::from_points(points), Some(expected));
+
+        let point = v(1.0, 2.0, 3.0);
+        assert_eq!(Cube3D::EMPTY.expanded_to_include(point), Cube3D { top: 3.0, left: 2.0, bottom: 3.0, right: 2.0, front: 1.0, back: 1.0 });
+
+        // Merging halves gives the same result
+        let a = points[..2].iter().fold(Cube3D::EMPTY, |cube, &p| cube.expanded_to_include(p));
+        let b = points[2..].iter().fold(Cube3D::EMPTY, |cube, &p| cube.expanded_to_include(p));
+        assert_eq!(a.merged(b), expected);
+        assert_eq!(b.merged(a), expected);
+
+        assert_eq!(expected.merged(Cube3D::EMPTY), expected);
+        assert_eq!(Cube3D::EMPTY.merged(expected), expected);
+        assert_eq!(Cube3D::EMPTY.merged(Cube3D::EMPTY), Cube3D::EMPTY);
+    }
+
+    #[test]
+    fn vector_assign_operators() {
+        let mut sum = Vector3D::ZEROED;
+        for v in [Vector3D { x: 1.0, y: 2.0, z: 3.0 }, Vector3D { x: -4.0, y: 0.5, z: 0.0 }, Vector3D::from_scalar(0.25)] {
+            sum += v;
+        }
+        assert_eq!(sum, Vector3D { x: -2.75, y: 2.75, z: 3.25 });
+        sum -= Vector3D::from_scalar(0.25);
+        assert_eq!(sum, Vector3D { x: -3.0, y: 2.5, z: 3.0 });
+
+        let mut sum = Vector2D::ZEROED;
+        for v in [Vector2D { x: 1.0, y: 2.0 }, Vector2D { x: -4.0, y: 0.5 }, Vector2D::from_scalar(0.25)] {
+            sum += v;
+        }
+        assert_eq!(sum, Vector2D { x: -2.75, y: 2.75 });
+        sum -= Vector2D::from_scalar(0.25);
+        assert_eq!(sum, Vector2D { x: -3.0, y: 2.5 });
+    }
+
+    #[test]
+    fn plane_split_triangle() {
+        let plane = Plane3D { vector: Vector3D { x: 0.0, y: 0.0, z: 1.0 }, offset: 1.0 };
+        let v = |x, y, z| Vector3D { x, y, z };
+
+        assert_eq!(plane.split_triangle(v(0.0, 0.0, 2.0), v(1.0, 0.0, 3.0), v(0.0, 1.0, 2.0)), TriangleSplit::Front);
+        assert_eq!(plane.split_triangle(v(0.0, 0.0, 0.0), v(1.0, 0.0, -3.0), v(0.0, 1.0, 0.5)), TriangleSplit::Back);
+        assert_eq!(plane.split_triangle(v(0.0, 0.0, 1.0), v(1.0, 0.0, 1.0), v(0.0, 1.0, 1.0)), TriangleSplit::Coplanar);
+
+        // vertices on the plane with the rest on one side do not split
+        assert_eq!(plane.split_triangle(v(0.0, 0.0, 1.0), v(1.0, 0.0, 1.0), v(0.0, 1.0, 2.0)), TriangleSplit::Front);
+        assert_eq!(plane.split_triangle(v(0.0, 0.0, 1.0), v(1.0, 0.0, 0.0), v(0.0, 1.0, 1.00001)), TriangleSplit::Back);
+
+        // two edges crossing
+        assert_eq!(
+            plane.split_triangle(v(0.0, 0.0, 0.0), v(2.0, 0.0, 2.0), v(0.0, 2.0, 2.0)),
+            TriangleSplit::Split { intersections: [v(1.0, 0.0, 1.0), v(0.0, 1.0, 1.0)] }
+        );
+
+        // one vertex on the plane and the opposite edge crossing
+        assert_eq!(
+            plane.split_triangle(v(0.0, 0.0, 1.0), v(2.0, 0.0, 0.0), v(2.0, 2.0, 2.0)),
+            TriangleSplit::Split { intersections: [v(0.0, 0.0, 1.0), v(2.0, 1.0, 1.0)] }
+        );
+    }
+
+    #[test]
+    fn vector2d_int_conversion() {
+        let v = Vector2DInt { x: -32768, y: 32767 };
+        assert_eq!(v.as_vector2d(), Vector2D { x: -32768.0, y: 32767.0 });
+        assert_eq!(v.as_vector2d().to_vector2d_int_rounded(), Some(v));
+        assert_eq!(Vector2D::from(v), v.as_vector2d());
+
+        assert_eq!(Vector2D { x: 1.4, y: -1.6 }.to_vector2d_int_rounded(), Some(Vector2DInt { x: 1, y: -2 }));
+        assert_eq!(Vector2D { x: 2.5, y: -0.5 }.to_vector2d_int_rounded(), Some(Vector2DInt { x: 2, y: 0 }));
+        assert_eq!(Vector2D { x: 32767.4, y: -32768.4 }.to_vector2d_int_rounded(), Some(Vector2DInt { x: 32767, y: -32768 }));
+
+        assert_eq!(Vector2D { x: 32767.6, y: 0.0 }.to_vector2d_int_rounded(), None);
+        assert_eq!(Vector2D { x: 0.0, y: -40000.0 }.to_vector2d_int_rounded(), None);
+        assert_eq!(Vector2D { x: f32::NAN, y: 0.0 }.to_vector2d_int_rounded(), None);
+        assert_eq!(Vector2D { x: f32::INFINITY, y: 0.0 }.to_vector2d_int_rounded(), None);
+
+        let a = Vector2DInt { x: 10, y: -20 };
+        let b = Vector2DInt { x: 5, y: 7 };
+        assert_eq!(a + b, Vector2DInt { x: 15, y: -13 });
+        assert_eq!(a - b, Vector2DInt { x: 5, y: -27 });
+        assert_eq!(Vector2DInt { x: i16::MAX, y: i16::MIN } + Vector2DInt { x: 1, y: 0 }, Vector2DInt { x: i16::MIN, y: i16::MIN });
+        assert_eq!(Vector2DInt { x: 0, y: i16::MIN } - Vector2DInt { x: 0, y: 1 }, Vector2DInt { x: 0, y: i16::MAX });
+    }
+
+    #[test]
+    fn matrix2x3_as_matrix3x3() {
+        assert_eq!(Matrix2x3::IDENTITY.as_matrix3x3(), Matrix3x3::IDENTITY);
+
+        // rotate 90 degrees around the Z axis, then tilt 30 degrees around the new left axis
+        let yaw = Quaternion { vector: Vector3D { x: 0.0, y: 0.0, z: Angle::_45_DEG.radians().fw_sin() }, w: Angle::_45_DEG.radians().fw_cos() };
+        let pitch_angle = Angle::from_degrees(15.0).radians();
+        let pitch = Quaternion { vector: Vector3D { x: 0.0, y: pitch_angle.fw_sin(), z: 0.0 }, w: pitch_angle.fw_cos() };
+        let full = yaw.as_matrix().multiply(&pitch.as_matrix());
+
+        let compact = Matrix2x3 { forward: full.forward, up: full.up };
+        assert_matrix_similar(compact.as_matrix3x3(), full, 0.00001);
+        assert_matrix_similar(Matrix3x3::from(compact), full, 0.00001);
+
+        for v in [Vector3D { x: 1.0, y: 2.0, z: 3.0 }, Vector3D { x: -0.5, y: 0.0, z: 4.0 }] {
+            let a = compact.transform_vector(v);
+            let b = full.transform_vector(v);
+            assert_similar!(a.x, b.x, 0.0001);
+            assert_similar!(a.y, b.y, 0.0001);
+            assert_similar!(a.z, b.z, 0.0001);
+        }
+    }
+
+    #[test]
+    fn matrix_orthonormalized() {
+        let drifted = Matrix3x3 {
+            forward: Vector3D { x: 0.98, y: 0.05, z: -0.02 },
+            left: Vector3D { x: -0.03, y: 1.03, z: 0.01 },
+            up: Vector3D { x: 0.04, y: 0.02, z: 0.97 }
+        };
+        let fixed = drifted.orthonormalized();
+
+        for row in [fixed.forward, fixed.left, fixed.up] {
+            assert_similar!(row.magnitude(), 1.0, 0.00001);
+        }
+        assert_similar!(fixed.forward.dot(fixed.left), 0.0, 0.00001);
+        assert_similar!(fixed.forward.dot(fixed.up), 0.0, 0.00001);
+        assert_similar!(fixed.left.dot(fixed.up), 0.0, 0.00001);
+        assert_similar!(fixed.determinant(), 1.0, 0.00001);
+
+        // It should stay close to the original
+        assert_matrix_similar(fixed, drifted, 0.05);
+
+        // Already orthonormal matrices are unchanged
+        assert_matrix_similar(Matrix3x3::IDENTITY.orthonormalized(), Matrix3x3::IDENTITY, 0.0);
+
+        let degenerate = Matrix3x3 { forward: Vector3D::from_scalar(1.0), left: Vector3D::ZEROED, up: Vector3D::from_scalar(2.0) };
+        assert_eq!(degenerate.orthonormalized(), Matrix3x3::IDENTITY);
+        assert_eq!(Matrix3x3::default().orthonormalized(), Matrix3x3::IDENTITY);
+    }
+
+    #[test]
+    fn quaternion_average() {
+        let assert_quaternion_similar = |a: Quaternion, b: Quaternion| {
+            assert_similar!(a.vector.x, b.vector.x, 0.0001);
+            assert_similar!(a.vector.y, b.vector.y, 0.0001);
+            assert_similar!(a.vector.z, b.vector.z, 0.0001);
+            assert_similar!(a.w, b.w, 0.0001);
+        };
+
+        let z = Vector3D { x: 0.0, y: 0.0, z: 1.0 };
+        let a = Quaternion::from_axis_angle(z, Angle::from_degrees(10.0));
+        let b = Quaternion::from_axis_angle(z, Angle::from_degrees(30.0));
+
+        let average = Quaternion::average(&[(a, 1.0), (b, 1.0)]).unwrap();
+        assert_quaternion_similar(average, a.interpolated(b, 0.5));
+        assert_quaternion_similar(average, Quaternion::from_axis_angle(z, Angle::from_degrees(20.0)));
+
+        // Order and sign do not matter
+        assert_quaternion_similar(Quaternion::average(&[(-b, 1.0), (a, 1.0)]).unwrap(), average);
+
+        // Weights are relative
+        assert_quaternion_similar(Quaternion::average(&[(a, 0.5), (b, 0.5)]).unwrap(), average);
+        assert_quaternion_similar(Quaternion::average(&[(a, 3.0), (b, 0.0)]).unwrap(), a);
+
+        assert_eq!(Quaternion::average(&[]), None);
+        assert_eq!(Quaternion::average(&[(a, 0.0), (b, 0.0)]), None);
+    }
+
+    #[test]
+    fn quaternion_to_axis_angle() {
+        let y = Vector3D { x: 0.0, y: 1.0, z: 0.0 };
+        let (axis, angle) = Quaternion::from_axis_angle(y, Angle::_90_DEG).to_axis_angle();
+        assert_similar!(axis.x, 0.0, 0.00001);
+        assert_similar!(axis.y, 1.0, 0.00001);
+        assert_similar!(axis.z, 0.0, 0.00001);
+        assert_similar!(angle.degrees(), 90.0, 0.001);
+
+        // Rotating backwards around an axis is the same as rotating forwards around the opposite axis
+        let (axis, angle) = Quaternion::from_axis_angle(y, Angle::from_degrees(-30.0)).to_axis_angle();
+        assert_similar!(axis.y, -1.0, 0.00001);
+        assert_similar!(angle.degrees(), 30.0, 0.001);
+
+        // Not normalized
+        let q = Quaternion::from_axis_angle(Vector3D { x: 1.0, y: 1.0, z: 0.0 }, Angle::from_degrees(120.0)) * 3.0;
+        let (axis, angle) = q.to_axis_angle();
+        assert_similar!(axis.x, core::f32::consts::FRAC_1_SQRT_2, 0.00001);
+        assert_similar!(axis.y, core::f32::consts::FRAC_1_SQRT_2, 0.00001);
+        assert_similar!(angle.degrees(), 120.0, 0.001);
+        assert_matrix_similar(Matrix3x3::from_axis_angle(axis, angle), q.as_matrix(), 0.00001);
+
+        let (axis, angle) = Quaternion::IDENTITY.to_axis_angle();
+        assert_eq!(angle, Angle::_0_DEG);
+        assert_similar!(axis.magnitude(), 1.0, 0.00001);
+    }
+
+    #[test]
+    fn matrix4x3_identity() {
+        let point = Vector3D { x: 1.5, y: -2.0, z: 30.0 };
+        assert_eq!(Matrix4x3::IDENTITY.transform_point(point), point);
+        assert_eq!(Matrix4x3::IDENTITY.transform_normal(point), point);
+        assert!(Matrix4x3::IDENTITY.is_identity());
+        assert!(Matrix4x3::from_matrix3x3(Matrix3x3::IDENTITY).is_identity());
+
+        let mut nearly = Matrix4x3::IDENTITY;
+        nearly.scale = 1.00001;
+        nearly.rotation.left.x = -0.0002;
+        nearly.position.z = 0.0005;
+        assert!(nearly.is_identity());
+
+        let mut moved = Matrix4x3::IDENTITY;
+        moved.position.x = 0.01;
+        assert!(!moved.is_identity());
+
+        let mut scaled = Matrix4x3::IDENTITY;
+        scaled.scale = 2.0;
+        assert!(!scaled.is_identity());
+
+        let rotated = Matrix4x3::from_matrix3x3(Matrix3x3::from_axis_angle(point, Angle::from_degrees(5.0)));
+        assert!(!rotated.is_identity());
+    }
+
+    #[test]
+    fn matrix4x3_transform_points() {
+        let matrix = Matrix4x3 {
+            scale: 1.5,
+            rotation: Matrix3x3::from_axis_angle(Vector3D { x: 0.2, y: -0.5, z: 1.0 }.normalized().unwrap(), Angle::from_degrees(33.0)),
+            position: Vector3D { x: 10.0, y: -3.0, z: 0.25 }
+        };
+
+        let input = [
+            Vector3D::ZEROED,
+            Vector3D { x: 1.0, y: 0.0, z: 0.0 },
+            Vector3D { x: -4.5, y: 2.25, z: 100.0 },
+            Vector3D { x: 0.001, y: -7.0, z: -0.5 }
+        ];
+        let mut output = [Vector3D::ZEROED; 4];
+        matrix.transform_points(&input, &mut output);
+
+        for (point, result) in input.iter().zip(output.iter()) {
+            assert_eq!(*result, matrix.transform_point(*point));
+        }
+
+        matrix.transform_points(&[], &mut []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn matrix4x3_transform_points_length_mismatch() {
+        let mut output = [Vector3D::ZEROED; 1];
+        Matrix4x3::IDENTITY.transform_points(&[Vector3D::ZEROED; 2], &mut output);
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = Vector2D { x: 1.0, y: -2.0 };
+        assert!(a.approx_eq(a, 0.0));
+        assert!(a.approx_eq(Vector2D { x: 1.00001, y: -2.00001 }, 0.0001));
+        assert!(!a.approx_eq(Vector2D { x: 1.0, y: -2.01 }, 0.0001));
+        assert!(!a.approx_eq(Vector2D { x: f32::NAN, y: -2.0 }, f32::INFINITY));
+
+        let b = Vector3D { x: 1.0, y: -2.0, z: 3.0 };
+        assert!(b.approx_eq(b, 0.0));
+        assert!(b.approx_eq(Vector3D { x: 1.00001, y: -2.00001, z: 2.99999 }, 0.0001));
+        assert!(!b.approx_eq(Vector3D { x: 1.0, y: -2.0, z: 3.01 }, 0.0001));
+        assert!(b.approx_eq(Vector3D { x: 1.0, y: -2.0, z: 3.01 }, 0.1), "epsilon should be configurable");
+
+        let c = Vector4D { x: 1.0, y: -2.0, z: 3.0, w: 4.0 };
+        assert!(c.approx_eq(c, 0.0));
+        assert!(c.approx_eq(Vector4D { x: 1.00001, y: -2.0, z: 3.0, w: 4.00001 }, 0.0001));
+        assert!(!c.approx_eq(Vector4D { x: 1.0, y: -2.0, z: 3.0, w: 4.01 }, 0.0001));
+
+        let q = Quaternion::from_axis_angle(Vector3D { x: 0.0, y: 0.0, z: 1.0 }, Angle::from_degrees(30.0));
+        assert!(q.approx_eq(Quaternion::from_axis_angle(Vector3D { x: 0.0, y: 0.0, z: 1.0 }, Angle::from_degrees(30.001)), 0.0001));
+        assert!(!q.approx_eq(Quaternion::from_axis_angle(Vector3D { x: 0.0, y: 0.0, z: 1.0 }, Angle::from_degrees(31.0)), 0.0001));
+        assert!(!q.approx_eq(-q, 0.0001), "negated quaternions are not approximately equal");
+
+        let m = Matrix3x3::from_axis_angle(Vector3D { x: 0.0, y: 1.0, z: 0.0 }, Angle::from_degrees(30.0));
+        assert!(m.approx_eq(Matrix3x3::from_axis_angle(Vector3D { x: 0.0, y: 1.0, z: 0.0 }, Angle::from_degrees(30.001)), 0.0001));
+        assert!(!m.approx_eq(Matrix3x3::from_axis_angle(Vector3D { x: 0.0, y: 1.0, z: 0.0 }, Angle::from_degrees(31.0)), 0.0001));
+        assert!(!m.approx_eq(Matrix3x3::IDENTITY, 0.0001));
+    }
+
+    #[test]
+    fn normalized_and_magnitude() {
+        let velocity = Vector3D { x: 3.0, y: -4.0, z: 12.0 };
+        let (direction, speed) = velocity.normalized_and_magnitude().unwrap();
+        assert_eq!(speed, 13.0);
+        assert_eq!(speed, velocity.magnitude());
+        assert_eq!(Some(direction), velocity.normalized());
+        assert_similar!(direction.magnitude(), 1.0, 0.00001);
+        assert_eq!(Vector3D::ZEROED.normalized_and_magnitude(), None);
+
+        let velocity = Vector2D { x: -6.0, y: 8.0 };
+        let (direction, speed) = velocity.normalized_and_magnitude().unwrap();
+        assert_eq!(speed, 10.0);
+        assert_eq!(speed, velocity.magnitude());
+        assert_eq!(direction, Vector2D { x: -0.6, y: 0.8 });
+        assert_similar!(direction.magnitude(), 1.0, 0.00001);
+        assert_eq!(Vector2D { x: 0.00001, y: 0.0 }.normalized_and_magnitude(), None);
+    }
+
+    #[test]
+    fn matrix3x3_array_inverse() {
+        // The previous implementation, which transmuted to and from an array
+        fn inverted_transmute(matrix: Matrix3x3) -> Matrix3x3 {
+            let determinant_inverse = 1.0 / matrix.determinant();
+            let array: [[f32; 3]; 3] = unsafe { core::mem::transmute(matrix) };
+            let mut inverse = [[0.0f32; 3]; 3];
+            let mut i = 0;
+            while i < 3 {
+                let mut j = 0;
+                while j < 3 {
+                    let (ip, im, jp, jm) = ((i + 1) % 3, (i + 2) % 3, (j + 1) % 3, (j + 2) % 3);
+                    inverse[j][i] = determinant_inverse * (array[ip][jp] * array[im][jm] - array[ip][jm] * array[im][jp]);
+                    j += 1;
+                }
+                i += 1;
+            }
+            unsafe { core::mem::transmute(inverse) }
+        }
+
+        let m = Matrix3x3 {
+            forward: Vector3D { x: 2.0, y: 0.5, z: -1.0 },
+            left: Vector3D { x: 0.25, y: 3.0, z: 0.75 },
+            up: Vector3D { x: -0.5, y: 1.5, z: 4.0 }
+        };
+        assert_eq!(m.as_array(), [[2.0, 0.5, -1.0], [0.25, 3.0, 0.75], [-0.5, 1.5, 4.0]]);
+        assert_eq!(Matrix3x3::from_array(m.as_array()), m);
+
+        let inverse = m.inverted();
+        let expected = inverted_transmute(m);
+        for (a, b) in inverse.as_array().iter().flatten().zip(expected.as_array().iter().flatten()) {
+            assert_eq!(a.to_bits(), b.to_bits());
+        }
+        assert_matrix_similar(m.multiply(&inverse), Matrix3x3::IDENTITY, 0.00001);
+
+        let rotation = Matrix3x3::from_axis_angle(Vector3D { x: 1.0, y: 2.0, z: 3.0 }, Angle::from_degrees(40.0));
+        assert_eq!(rotation.inverted(), inverted_transmute(rotation));
+    }
+
+    #[test]
+    fn look_at() {
+        let x = Vector3D { x: 1.0, y: 0.0, z: 0.0 };
+        let z = Vector3D { x: 0.0, y: 0.0, z: 1.0 };
+        assert_eq!(Matrix3x3::look_at(x.scaled(3.0), z), Some(Matrix3x3::IDENTITY));
+
+        // Up does not need to be perpendicular
+        let forward = Vector3D { x: 1.0, y: 1.0, z: 0.0 };
+        let matrix = Matrix3x3::look_at(forward, Vector3D { x: 0.5, y: 0.5, z: 1.0 }).unwrap();
+        assert_similar!(matrix.forward.x, core::f32::consts::FRAC_1_SQRT_2, 0.00001);
+        assert_similar!(matrix.forward.y, core::f32::consts::FRAC_1_SQRT_2, 0.00001);
+        assert_similar!(matrix.up.dot(matrix.forward), 0.0, 0.00001);
+        assert_similar!(matrix.up.z, 1.0, 0.00001);
+        assert_similar!(matrix.determinant(), 1.0, 0.00001);
+
+        assert_eq!(Matrix3x3::look_at(z, z.scaled(2.0)), None);
+        assert_eq!(Matrix3x3::look_at(z.negated(), z), None);
+        assert_eq!(Matrix3x3::look_at(Vector3D::ZEROED, z), None);
+    }
+
+    #[test]
+    fn from_axis_angle() {
+        let v = |x, y, z| Vector3D { x, y, z };
+        let assert_vector_similar = |a: Vector3D, b: Vector3D| {
+            assert_similar!(a.x, b.x, 0.00001);
+            assert_similar!(a.y, b.y, 0.00001);
+            assert_similar!(a.z, b.z, 0.00001);
+        };
+
+        let x = v(1.0, 0.0, 0.0);
+        let y = v(0.0, 1.0, 0.0);
+        let z = v(0.0, 0.0, 1.0);
+
+        let about_z = Matrix3x3::from_axis_angle(z, Angle::_90_DEG);
+        assert_vector_similar(about_z.transform_vector(x), y);
+        assert_vector_similar(about_z.transform_vector(y), x.negated());
+        assert_vector_similar(about_z.transform_vector(z), z);
+
+        let about_x = Matrix3x3::from_axis_angle(x, Angle::_90_DEG);
+        assert_vector_similar(about_x.transform_vector(y), z);
+        assert_vector_similar(about_x.transform_vector(z), y.negated());
+
+        let about_y = Matrix3x3::from_axis_angle(y, Angle::_90_DEG);
+        assert_vector_similar(about_y.transform_vector(z), x);
+        assert_vector_similar(about_y.transform_vector(x), z.negated());
+
+        // Same as yaw
+        let yaw = Euler3D { yaw: Angle::from_degrees(30.0), pitch: Angle::_0_DEG, roll: Angle::_0_DEG }.to_matrix();
+        assert_matrix_similar(Matrix3x3::from_axis_angle(z.scaled(5.0), Angle::from_degrees(30.0)), yaw, 0.00001);
+
+        let axis = v(1.0, -2.0, 3.0);
+        let angle = Angle::from_degrees(75.0);
+        let matrix = Matrix3x3::from_axis_angle(axis, angle);
+        assert_similar!(matrix.determinant(), 1.0, 0.00001);
+        assert_vector_similar(matrix.transform_vector(axis), axis);
+        assert_matrix_similar(Quaternion::from_axis_angle(axis, angle).as_matrix(), matrix, 0.00001);
+        assert_matrix_similar(Quaternion::from_axis_angle(z, Angle::_90_DEG).as_matrix(), about_z, 0.00001);
+
+        assert_eq!(Matrix3x3::from_axis_angle(Vector3D::ZEROED, angle), Matrix3x3::IDENTITY);
+        assert_eq!(Quaternion::from_axis_angle(Vector3D::ZEROED, angle), Quaternion::IDENTITY);
+    }
+
+    #[test]
+    fn snapped() {
+        let v = Vector3D { x: 1.4, y: -2.6, z: 0.2 };
+        assert_eq!(v.snapped(1.0), Vector3D { x: 1.0, y: -3.0, z: 0.0 });
+        assert_eq!(v.snapped(0.5), Vector3D { x: 1.5, y: -2.5, z: 0.0 });
+        assert_eq!(v.snapped(0.0), v);
+    }
+
+    #[test]
+    fn slerp() {
+        let x = Vector3D { x: 1.0, y: 0.0, z: 0.0 };
+        let y = Vector3D { x: 0.0, y: 1.0, z: 0.0 };
+
+        let quarter = x.slerp(y, 0.25);
+        let angle = core::f32::consts::FRAC_PI_8;
+        assert_similar!(quarter.x, angle.fw_cos(), 0.00001);
+        assert_similar!(quarter.y, angle.fw_sin(), 0.00001);
+        assert_similar!(quarter.z, 0.0, 0.00001);
+        assert_similar!(quarter.magnitude(), 1.0, 0.00001);
+
+        let half = x.slerp(y, 0.5);
+        assert_similar!(half.x, half.y, 0.00001);
+        assert_similar!(half.magnitude(), 1.0, 0.00001);
+
+        assert_eq!(x.slerp(y, 0.0), x);
+        assert_similar!(x.slerp(y, 1.0).y, 1.0, 0.00001);
+
+        // Parallel vectors fall back to linear interpolation
+        assert_eq!(x.slerp(x, 0.25), x);
+        assert_eq!(x.slerp(x.negated(), 0.25), x.linear_interpolated(x.negated(), 0.25));
+    }
+
+    #[test]
+    fn distance_between_points() {
+        let a = Vector3D { x: 1.0, y: 2.0, z: 3.0 };
+        assert_eq!(a.distance_to(Vector3D { x: 1.0, y: -2.0, z: 3.0 }), 4.0);
+        assert_eq!(a.distance_squared_to(Vector3D { x: 1.0, y: -2.0, z: 3.0 }), 16.0);
+        assert_eq!(a.distance_to(Vector3D { x: 3.0, y: 4.0, z: 4.0 }), 3.0);
+        assert_eq!(a.distance_squared_to(Vector3D { x: 3.0, y: 4.0, z: 4.0 }), 9.0);
+        assert_eq!(a.distance_to(a), 0.0);
+
+        let b = Vector2D { x: -1.0, y: 1.0 };
+        assert_eq!(b.distance_to(Vector2D { x: 4.0, y: 1.0 }), 5.0);
+        assert_eq!(b.distance_to(Vector2D { x: 2.0, y: 5.0 }), 5.0);
+        assert_eq!(b.distance_squared_to(Vector2D { x: 2.0, y: 5.0 }), 25.0);
+        assert_eq!(Vector2D { x: 2.0, y: 5.0 }.distance_to(b), 5.0);
+    }
+
+    #[test]
+    fn component_min_max_clamp() {
+        let a = Vector3D { x: -1.0, y: 4.0, z: -3.0 };
+        let b = Vector3D { x: 2.0, y: -5.0, z: -6.0 };
+        assert_eq!(a.component_min(b), Vector3D { x: -1.0, y: -5.0, z: -6.0 });
+        assert_eq!(a.component_max(b), Vector3D { x: 2.0, y: 4.0, z: -3.0 });
+
+        let min = Vector3D { x: -2.0, y: -2.0, z: -2.0 };
+        let max = Vector3D { x: 2.0, y: 2.0, z: 2.0 };
+        assert_eq!(a.clamped(min, max), Vector3D { x: -1.0, y: 2.0, z: -2.0 });
+        assert_eq!(b.clamped(min, max), Vector3D { x: 2.0, y: -2.0, z: -2.0 });
+
+        let a = Vector2D { x: -1.0, y: 4.0 };
+        let b = Vector2D { x: 2.0, y: -5.0 };
+        assert_eq!(a.component_min(b), Vector2D { x: -1.0, y: -5.0 });
+        assert_eq!(a.component_max(b), Vector2D { x: 2.0, y: 4.0 });
+        assert_eq!(a.clamped(Vector2D { x: 0.0, y: -3.0 }, Vector2D { x: 1.0, y: 3.0 }), Vector2D { x: 0.0, y: 3.0 });
+        assert_eq!(b.clamped(Vector2D { x: 0.0, y: -3.0 }, Vector2D { x: 1.0, y: 3.0 }), Vector2D { x: 1.0, y: -3.0 });
+    }
+
+    #[test]
+    fn reflect_and_project() {
+        let v = |x, y, z| Vector3D { x, y, z };
+        let up = v(0.0, 0.0, 1.0);
+
+        let velocity = v(3.0, -2.0, -5.0);
+        assert_eq!(velocity.reflected(up), v(3.0, -2.0, 5.0));
+        assert_eq!(velocity.reflected(up.negated()), v(3.0, -2.0, 5.0));
+        assert_eq!(v(3.0, -2.0, 0.0).reflected(up), v(3.0, -2.0, 0.0));
+
+        let diagonal = v(2.0, 2.0, 2.0);
+        let x = v(1.0, 0.0, 0.0);
+        assert_eq!(diagonal.projected_onto(x), v(2.0, 0.0, 0.0));
+        assert_eq!(diagonal.rejected_from(x), v(0.0, 2.0, 2.0));
+        assert_eq!(diagonal.projected_onto(x) + diagonal.rejected_from(x), diagonal);
+        assert_eq!(diagonal.rejected_from(x).dot(x), 0.0);
+
+        // Sliding along a wall is the rejection from its normal
+        assert_eq!(velocity.rejected_from(up), v(3.0, -2.0, 0.0));
+        assert_eq!(velocity.projected_onto(up), v(0.0, 0.0, -5.0));
+    }
+
+    #[test]
+    fn barycentric_weights() {
+        let a = Vector2D { x: 0.0, y: 0.0 };
+        let b = Vector2D { x: 3.0, y: 0.0 };
+        let c = Vector2D { x: 0.0, y: 6.0 };
+
+        let centroid = Vector2D { x: 1.0, y: 2.0 };
+        let (wa, wb, wc) = barycentric(centroid, a, b, c).unwrap();
+        assert_similar!(wa, 1.0 / 3.0, 0.00001);
+        assert_similar!(wb, 1.0 / 3.0, 0.00001);
+        assert_similar!(wc, 1.0 / 3.0, 0.00001);
+
+        // Winding does not matter
+        let (wa, wc, wb) = barycentric(centroid, a, c, b).unwrap();
+        assert_similar!(wa, 1.0 / 3.0, 0.00001);
+        assert_similar!(wb, 1.0 / 3.0, 0.00001);
+        assert_similar!(wc, 1.0 / 3.0, 0.00001);
+
+        assert_eq!(barycentric(b, a, b, c), Some((0.0, 1.0, 0.0)));
+        assert_eq!(barycentric(c, a, b, c), Some((0.0, 0.0, 1.0)));
+
+        // Outside the triangle, one weight goes negative
+        let (wa, wb, wc) = barycentric(Vector2D { x: 3.0, y: 6.0 }, a, b, c).unwrap();
+        assert!(wa < 0.0);
+        assert_similar!(wa + wb + wc, 1.0, 0.00001);
+
+        assert_eq!(barycentric(centroid, a, Vector2D { x: 1.0, y: 1.0 }, Vector2D { x: 2.0, y: 2.0 }), None);
+    }
+
+    #[test]
+    fn polygon_area_and_winding() {
+        let v = |x, y| Vector2D { x, y };
+
+        let ccw = [v(0.0, 0.0), v(1.0, 0.0), v(1.0, 1.0), v(0.0, 1.0)];
+        assert_eq!(polygon_signed_area(&ccw), 1.0);
+        assert!(!polygon_is_clockwise(&ccw));
+
+        let cw = [v(0.0, 0.0), v(0.0, 1.0), v(1.0, 1.0), v(1.0, 0.0)];
+        assert_eq!(polygon_signed_area(&cw), -1.0);
+        assert!(polygon_is_clockwise(&cw));
+
+        // Area does not depend on where the polygon is
+        let moved = ccw.map(|p| p + v(-50.0, 20.0));
+        assert_eq!(polygon_signed_area(&moved), 1.0);
+
+        let colinear = [v(0.0, 0.0), v(1.0, 1.0), v(3.0, 3.0)];
+        assert_eq!(polygon_signed_area(&colinear), 0.0);
+        assert!(!polygon_is_clockwise(&colinear));
+
+        assert_eq!(polygon_signed_area(&[]), 0.0);
+        assert_eq!(polygon_signed_area(&[v(1.0, 2.0)]), 0.0);
+        assert_eq!(polygon_signed_area(&[v(1.0, 2.0), v(3.0, 4.0)]), 0.0);
+    }
+
+    #[test]
+    fn centroids() {
+        let v = |x, y, z| Vector3D { x, y, z };
+
+        let octahedron = [v(1.0, 0.0, 0.0), v(-1.0, 0.0, 0.0), v(0.0, 2.0, 0.0), v(0.0, -2.0, 0.0), v(0.0, 0.0, 3.0), v(0.0, 0.0, -3.0)];
+        assert_eq!(centroid(&octahedron), Some(Vector3D::ZEROED));
+
+        let moved = octahedron.map(|p| p + v(10.0, -5.0, 0.5));
+        assert_eq!(centroid(&moved), Some(v(10.0, -5.0, 0.5)));
+        assert_eq!(centroid(&[v(1.5, 2.5, -3.5)]), Some(v(1.5, 2.5, -3.5)));
+        assert_eq!(centroid(&[]), None);
+
+        // Large offsets shouldn't swallow small ones
+        let mut cloud = [v(0.0, 0.0, 0.0); 1001];
+        cloud[0] = v(100000.0, 0.0, 0.0);
+        cloud[1] = v(-100000.0, 0.0, 0.0);
+        cloud[2..].fill(v(0.001, 0.0, 0.0));
+        assert_similar!(centroid(&cloud).unwrap().x, 0.999 / 1001.0, 0.0000001);
+
+        let square = [Vector2D { x: 1.0, y: 1.0 }, Vector2D { x: -1.0, y: 1.0 }, Vector2D { x: -1.0, y: -1.0 }, Vector2D { x: 1.0, y: -1.0 }];
+        assert_eq!(centroid_2d(&square), Some(Vector2D { x: 0.0, y: 0.0 }));
+        assert_eq!(centroid_2d(&[Vector2D { x: 4.0, y: -2.0 }, Vector2D { x: 6.0, y: 0.0 }]), Some(Vector2D { x: 5.0, y: -1.0 }));
+        assert_eq!(centroid_2d(&[]), None);
+    }
+
+    #[test]
+    fn plane2d_classify() {
+        let plane = Plane2D { vector: Vector2D { x: 0.0, y: 1.0 }, offset: 2.0 };
+
+        let front = Vector2D { x: 5.0, y: 3.5 };
+        assert_eq!(plane.distance_to_point(front), 1.5);
+        assert_eq!(plane.classify(front), Ordering::Greater);
+
+        let on = Vector2D { x: -100.0, y: 2.0 };
+        assert_eq!(plane.distance_to_point(on), 0.0);
+        assert_eq!(plane.classify(on), Ordering::Equal);
+
+        let behind = Vector2D { x: 0.0, y: -1.0 };
+        assert_eq!(plane.distance_to_point(behind), -3.0);
+        assert_eq!(plane.classify(behind), Ordering::Less);
+
+        let diagonal = Plane2D { vector: Vector2D { x: 1.0, y: 1.0 }.normalized().unwrap(), offset: 0.0 };
+        assert_eq!(diagonal.classify(Vector2D { x: 1.0, y: 0.0 }), Ordering::Greater);
+        assert_eq!(diagonal.classify(Vector2D { x: -1.0, y: 0.5 }), Ordering::Less);
+        assert_eq!(diagonal.classify(Vector2D { x: f32::NAN, y: 0.0 }), Ordering::Equal);
+    }
+
+    #[test]
+    fn plane_normalized() {
+        let v = |x, y, z| Vector3D { x, y, z };
+
+        let plane = Plane3D { vector: v(0.0, 0.0, 2.0), offset: 4.0 };
+        let normalized = plane.normalized().unwrap();
+        assert_eq!(normalized, Plane3D { vector: v(0.0, 0.0, 1.0), offset: 2.0 });
+
+        for point in [v(1.0, 2.0, 5.0), v(-3.0, 0.0, 0.0), v(0.0, 0.0, 2.0)] {
+            assert_eq!(normalized.distance_to_point(point), plane.distance_to_point(point) * 0.5);
+        }
+        assert_eq!(normalized.distance_to_point(v(7.0, -1.0, 5.0)), 3.0);
+
+        let tilted = Plane3D { vector: v(3.0, 0.0, 4.0), offset: -10.0 }.normalized().unwrap();
+        assert_similar!(tilted.vector.magnitude(), 1.0, 0.00001);
+        assert_similar!(tilted.offset, -2.0, 0.00001);
+
+        assert_eq!(normalized.normalized(), Some(normalized));
+        assert_eq!(Plane3D { vector: Vector3D::ZEROED, offset: 1.0 }.normalized(), None);
+    }
+
+    #[test]
+    fn plane_from_points() {
+        let v = |x, y, z| Vector3D { x, y, z };
+
+        let plane = Plane3D::from_points(v(0.0, 0.0, 2.0), v(3.0, 0.0, 2.0), v(0.0, 3.0, 2.0)).unwrap();
+        assert_eq!(plane, Plane3D { vector: v(0.0, 0.0, 1.0), offset: 2.0 });
+        assert_eq!(plane.distance_to_point(v(5.0, -5.0, 3.0)), 1.0);
+        assert_eq!(plane.distance_to_point(v(5.0, -5.0, 0.0)), -2.0);
+
+        // Reversing the winding flips the plane
+        let flipped = Plane3D::from_points(v(0.0, 0.0, 2.0), v(0.0, 3.0, 2.0), v(3.0, 0.0, 2.0)).unwrap();
+        assert_eq!(flipped, Plane3D { vector: v(0.0, 0.0, -1.0), offset: -2.0 });
+
+        let tilted = Plane3D::from_points(v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0), v(0.0, 0.0, 1.0)).unwrap();
+        for c in [tilted.vector.x, tilted.vector.y, tilted.vector.z] {
+            assert_similar!(c, 1.0 / 3.0f32.fw_sqrt(), 0.00001);
+        }
+        assert_similar!(tilted.distance_to_point(v(1.0, 0.0, 0.0)), 0.0, 0.00001);
+
+        assert_eq!(Plane3D::from_points(v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0), v(2.0, 2.0, 2.0)), None);
+        assert_eq!(Plane3D::from_points(v(1.0, 2.0, 3.0), v(1.0, 2.0, 3.0), v(4.0, 5.0, 6.0)), None);
+    }
+
+    #[test]
+    fn component_comparisons() {
+        let a = Vector3D { x: 1.0, y: 5.0, z: 3.0 };
+        let b = Vector3D::from_scalar(2.0);
+        assert_eq!(a.cmp_lt(b), (true, false, false));
+        assert_eq!(a.cmp_gt(b), (false, true, true));
+        assert_eq!(a.cmp_le(Vector3D { x: 1.0, y: 5.0, z: 2.0 }), (true, true, false));
+        assert_eq!(a.cmp_ge(Vector3D { x: 1.0, y: 5.0, z: 4.0 }), (true, true, false));
+
+        // NaN compares false for everything
+        assert_eq!(Vector3D::from_scalar(f32::NAN).cmp_le(b), (false, false, false));
+    }
+
+    #[test]
+    fn angle_arithmetic() {
+        assert_similar!((Angle::_90_DEG + Angle::_90_DEG).radians(), Angle::_180_DEG.radians(), 0.00001);
+        assert_similar!((Angle::_45_DEG * 2.0).radians(), Angle::_90_DEG.radians(), 0.00001);
+
+        let a = Angle::from_degrees(37.5);
+        let b = Angle::from_degrees(-112.25);
+        assert_similar!((a + b - b).radians(), a.radians(), 0.00001);
+        assert_similar!((a - b + b).radians(), a.radians(), 0.00001);
+
+        let mut c = a;
+        c += b;
+        c -= b;
+        assert_similar!(c.radians(), a.radians(), 0.00001);
+    }
+
+    #[test]
+    fn angle_turns() {
+        assert_similar!(Angle::from_turns(0.25).radians(), Angle::_90_DEG.radians(), 0.00001);
+        assert_similar!(Angle::from_turns(-0.5).radians(), -Angle::_180_DEG.radians(), 0.00001);
+        assert_similar!(Angle::from_turns(1.0).radians(), Angle::_360_DEG.radians(), 0.00001);
+        assert_similar!(Angle::_180_DEG.turns(), 0.5, 0.00001);
+        assert_similar!(Angle::_45_DEG.turns(), 0.125, 0.00001);
+        assert_similar!(Angle::from_degrees(720.0).turns(), 2.0, 0.00001);
+        assert_eq!(Angle::_0_DEG.turns(), 0.0);
+
+        let a = Angle::from_degrees(-37.5);
+        assert_similar!(Angle::from_turns(a.turns()).radians(), a.radians(), 0.00001);
+    }
+
+    #[test]
+    fn angle_trig() {
+        assert_eq!(Angle::_0_DEG.sin(), 0.0);
+        assert_eq!(Angle::_0_DEG.cos(), 1.0);
+        assert_eq!(Angle::_0_DEG.tan(), 0.0);
+
+        assert_similar!(Angle::_45_DEG.sin(), core::f32::consts::FRAC_1_SQRT_2, 0.00001);
+        assert_similar!(Angle::_45_DEG.cos(), core::f32::consts::FRAC_1_SQRT_2, 0.00001);
+        assert_similar!(Angle::_45_DEG.tan(), 1.0, 0.00001);
+
+        assert_similar!(Angle::_90_DEG.sin(), 1.0, 0.00001);
+        assert_similar!(Angle::_90_DEG.cos(), 0.0, 0.00001);
+
+        assert_similar!(Angle::_180_DEG.sin(), 0.0, 0.00001);
+        assert_similar!(Angle::_180_DEG.cos(), -1.0, 0.00001);
+        assert_similar!(Angle::_180_DEG.tan(), 0.0, 0.00001);
+
+        assert_similar!(Angle::_360_DEG.sin(), 0.0, 0.00001);
+        assert_similar!(Angle::_360_DEG.cos(), 1.0, 0.00001);
+
+        let a = Angle::from_degrees(-37.5);
+        assert_eq!(a.sin_cos(), (a.radians().fw_sin(), a.radians().fw_cos()));
+        assert_eq!(a.tan(), a.radians().fw_tan());
+    }
+
+    #[test]
+    fn euler2d_interpolation() {
+        let a = Euler2D { yaw: Angle::from_degrees(10.0), pitch: Angle::from_degrees(-20.0) };
+        let b = Euler2D { yaw: Angle::from_degrees(50.0), pitch: Angle::from_degrees(40.0) };
+        let mid = a.interpolated(b, 0.5);
+        assert_similar!(mid.yaw.degrees(), 30.0, 0.001);
+        assert_similar!(mid.pitch.degrees(), 10.0, 0.001);
+
+        // Yaw should take the short way around
+        let a = Euler2D { yaw: Angle::from_degrees(170.0), pitch: Angle::_0_DEG };
+        let b = Euler2D { yaw: Angle::from_degrees(-170.0), pitch: Angle::_0_DEG };
+        assert_similar!(a.interpolated(b, 0.5).yaw.degrees().abs(), 180.0, 0.001);
+        assert_similar!(a.interpolated(b, 0.25).yaw.degrees(), 175.0, 0.001);
+        assert_similar!(a.interpolated(b, 0.75).yaw.degrees(), -175.0, 0.001);
+        assert_similar!(b.interpolated(a, 0.25).yaw.degrees(), -175.0, 0.001);
+
+        // Pitch never leaves [-90, 90]
+        let a = Euler2D { yaw: Angle::_0_DEG, pitch: Angle::from_degrees(120.0) };
+        let b = Euler2D { yaw: Angle::_0_DEG, pitch: Angle::from_degrees(100.0) };
+        assert_similar!(a.interpolated(b, 0.5).pitch.degrees(), 90.0, 0.001);
+    }
+
+    #[test]
+    fn fixed_point_angles() {
+        assert_similar!(Angle::from_fixed(64, 8).degrees(), 90.0, 0.0001);
+        assert_similar!(Angle::from_fixed(-128, 8).degrees(), -180.0, 0.0001);
+        assert_similar!(Angle::from_fixed(0x2000, 14).degrees(), 180.0, 0.0001);
+        assert_eq!(Angle::_90_DEG.to_fixed(8), 64);
+        assert_eq!((-Angle::_180_DEG).to_fixed(14), -0x2000);
+
+        for bits in [8, 12, 15] {
+            for value in [i16::MIN, -1234, -1, 0, 1, 77, 4095, i16::MAX] {
+                assert_eq!(Angle::from_fixed(value, bits).to_fixed(bits), value, "{value} did not round trip at {bits} bits");
+            }
+        }
+
+        // Saturates instead of wrapping
+        assert_eq!(Angle::_360_DEG.to_fixed(15), i16::MAX);
+    }
+
+    #[test]
+    fn normalized_fast() {
+        for vector in [Vector3D { x: 3.0, y: 4.0, z: 0.0 }, Vector3D { x: 0.0, y: 0.0, z: -0.5 }, Vector3D { x: 100.0, y: -20.0, z: 7.0 }] {
+            let accurate = vector.normalized().unwrap();
+            let fast = vector.normalized_fast().unwrap();
+            assert_similar!(fast.x, accurate.x, 0.002);
+            assert_similar!(fast.y, accurate.y, 0.002);
+            assert_similar!(fast.z, accurate.z, 0.002);
+            assert_similar!(fast.magnitude(), 1.0, 0.002);
+        }
+        assert_eq!(Vector3D::ZEROED.normalized_fast(), None);
+
+        for vector in [Vector3D { x: -f32::NAN, y: 0.0, z: 0.0 }, Vector3D { x: 1.0, y: f32::NAN, z: 2.0 }] {
+            assert_eq!(vector.normalized_fast().is_some(), vector.normalized().is_some());
+            assert!(vector.normalized_fast().unwrap().x.is_nan());
+        }
+        assert_eq!(Vector3D { x: -0.0, y: -0.0, z: -0.0 }.normalized_fast(), None);
+        assert_eq!(Vector3D { x: -0.00001, y: 0.0, z: 0.0 }.normalized_fast(), Vector3D { x: -0.00001, y: 0.0, z: 0.0 }.normalized());
+    }
+
+    fn assert_matrix_similar(a: Matrix3x3, b: Matrix3x3, max: f32) {
+        for (a, b) in [(a.forward, b.forward), (a.left, b.left), (a.up, b.up)] {
+            assert_similar!(a.x, b.x, max);
+            assert_similar!(a.y, b.y, max);
+            assert_similar!(a.z, b.z, max);
+        }
+    }
+
+    #[test]
+    fn matrix_powf() {
+        let half_angle = 0.6f32;
+        let axis = Vector3D { x: 1.0, y: 2.0, z: 3.0 }.normalized().unwrap();
+        let m = Quaternion { vector: axis * half_angle.fw_sin(), w: half_angle.fw_cos() }.as_matrix();
+
+        let half = m.powf(0.5);
+        assert_matrix_similar(half.multiply(&half), m, 0.0001);
+        assert_matrix_similar(m.powf(1.0), m, 0.0001);
+        assert_matrix_similar(m.powf(2.0), m.multiply(&m), 0.0001);
+        assert_matrix_similar(m.powf(0.0), Matrix3x3::IDENTITY, 0.0001);
+        assert_matrix_similar(Matrix3x3::IDENTITY.powf(0.5), Matrix3x3::IDENTITY, 0.0001);
+    }
+
+    #[test]
+    fn vector_total_cmp() {
+        let a = Vector3D { x: 1.0, y: 2.0, z: 3.0 };
+        let b = Vector3D { x: 1.0, y: 2.0, z: 4.0 };
+        let c = Vector3D { x: 1.0, y: -2.0, z: 5.0 };
+        let d = Vector3D { x: -1.0, y: 9.0, z: 9.0 };
+        let nan = Vector3D { x: f32::NAN, y: 0.0, z: 0.0 };
+
+        let expected = [d, c, a, b];
+        let mut list = [b, a, d, c];
+        list.sort_by(|a, b| a.total_cmp(*b));
+        assert_eq!(list, expected);
+
+        let mut list = [c, b, d, a];
+        list.sort_by(|a, b| a.total_cmp(*b));
+        assert_eq!(list, expected);
+
+        let mut list = [nan, a, d];
+        list.sort_by(|a, b| a.total_cmp(*b));
+        assert_eq!(list[..2], [d, a]);
+        assert!(list[2].x.is_nan());
+
+        assert_eq!(a.total_cmp(a), Ordering::Equal);
+
+        // Differing only in z
+        assert_eq!(a.total_cmp(b), Ordering::Less);
+        assert_eq!(b.total_cmp(a), Ordering::Greater);
+
+        // NaNs are ordered consistently regardless of input order, with negative NaNs first
+        let z_nan = Vector3D { z: f32::NAN, ..a };
+        let z_neg_nan = Vector3D { z: -f32::NAN, ..a };
+        assert_eq!(z_nan.total_cmp(z_nan), Ordering::Equal);
+        assert_eq!(a.total_cmp(z_nan), Ordering::Less);
+        assert_eq!(z_neg_nan.total_cmp(a), Ordering::Less);
+
+        let mut forwards = [z_nan, b, z_neg_nan, a];
+        let mut backwards = [a, z_neg_nan, b, z_nan];
+        forwards.sort_by(|a, b| a.total_cmp(*b));
+        backwards.sort_by(|a, b| a.total_cmp(*b));
+        for (f, b) in forwards.iter().zip(backwards.iter()) {
+            assert_eq!(f.total_cmp(*b), Ordering::Equal);
+        }
+        assert!(forwards[0].z.is_nan() && forwards[0].z.is_sign_negative());
+        assert_eq!(forwards[1..3], [a, b]);
+        assert!(forwards[3].z.is_nan() && forwards[3].z.is_sign_positive());
+
+        let mut sorted = [z_nan, b, z_neg_nan, a];
+        sorted.sort_by(Vector3D::total_cmp_lexicographic);
+        for (s, f) in sorted.iter().zip(forwards.iter()) {
+            assert_eq!(s.total_cmp_lexicographic(f), Ordering::Equal);
+        }
+        assert_eq!(a.total_cmp_lexicographic(&b), Ordering::Less);
+        assert_eq!(b.total_cmp_lexicographic(&a), Ordering::Greater);
+    }
+
+    #[test]
+    fn euler2d_as_vector_with_up() {
+        let assert_vector_similar = |a: Vector3D, b: Vector3D| {
+            assert_similar!(a.x, b.x, 0.0001);
+            assert_similar!(a.y, b.y, 0.0001);
+            assert_similar!(a.z, b.z, 0.0001);
+        };
+
+        let forward = Euler2D { yaw: Angle::_0_DEG, pitch: Angle::_0_DEG };
+        let left = Euler2D { yaw: Angle::_90_DEG, pitch: Angle::_0_DEG };
+        let up = Euler2D { yaw: Angle::_0_DEG, pitch: Angle::_90_DEG };
+
+        assert_vector_similar(forward.as_vector_with_up(Vector3DComponent::Z), Vector3D { x: 1.0, y: 0.0, z: 0.0 });
+        assert_vector_similar(left.as_vector_with_up(Vector3DComponent::Z), Vector3D { x: 0.0, y: 1.0, z: 0.0 });
+        assert_vector_similar(up.as_vector_with_up(Vector3DComponent::Z), Vector3D { x: 0.0, y: 0.0, z: 1.0 });
+        assert_vector_similar(left.as_vector_with_up(Vector3DComponent::Z), left.as_vector());
+
+        assert_vector_similar(forward.as_vector_with_up(Vector3DComponent::Y), Vector3D { x: 0.0, y: 0.0, z: 1.0 });
+        assert_vector_similar(left.as_vector_with_up(Vector3DComponent::Y), Vector3D { x: 1.0, y: 0.0, z: 0.0 });
+        assert_vector_similar(up.as_vector_with_up(Vector3DComponent::Y), Vector3D { x: 0.0, y: 1.0, z: 0.0 });
+
+        assert_vector_similar(forward.as_vector_with_up(Vector3DComponent::X), Vector3D { x: 0.0, y: 1.0, z: 0.0 });
+        assert_vector_similar(up.as_vector_with_up(Vector3DComponent::X), Vector3D { x: 1.0, y: 0.0, z: 0.0 });
+    }
+
+    #[test]
+    fn decode_compressed_normals_buffer() {
+        let normals = [
+            Vector3D { x: 1.0, y: 0.0, z: 0.0 },
+            Vector3D { x: 0.0, y: -1.0, z: 0.0 },
+            Vector3D { x: 0.0, y: 0.0, z: 1.0 },
+            Vector3D { x: 0.6, y: 0.0, z: -0.8 },
+        ];
+        let compressed = normals.map(|n| n.compress());
+        let mut decompressed = [Vector3D::ZEROED; 4];
+
+        assert_eq!(decode_compressed_normals(&compressed, &mut decompressed), Some(()));
+        assert_eq!(decompressed, compressed.map(CompressedVector3D::decompress));
+        for (a, b) in decompressed.iter().zip(normals.iter()) {
+            assert_similar!(a.x, b.x, 0.002);
+            assert_similar!(a.y, b.y, 0.002);
+            assert_similar!(a.z, b.z, 0.002);
+        }
+
+        let mut too_short = [Vector3D::ZEROED; 3];
+        assert_eq!(decode_compressed_normals(&compressed, &mut too_short), None);
+        assert_eq!(too_short, [Vector3D::ZEROED; 3]);
+    }
+
+    #[test]
+    fn quaternion_rotate_towards() {
+        let yaw = |angle: Angle| {
+            let half = angle.radians() / 2.0;
+            Quaternion { vector: Vector3D { x: 0.0, y: 0.0, z: half.fw_sin() }, w: half.fw_cos() }
+        };
+
+        let start = Quaternion::IDENTITY;
+        let target = yaw(Angle::from_degrees(30.0));
+        assert_similar!(start.angle_to(target).degrees(), 30.0, 0.01);
+        assert_similar!(target.angle_to(-target).degrees(), 0.0, 0.01);
+
+        // Within the step, so it should snap to the target
+        assert_eq!(start.rotate_towards(target, Angle::from_degrees(45.0)), target.normalized());
+        assert_eq!(start.rotate_towards(target, Angle::from_degrees(30.0)), target.normalized());
+
+        // Otherwise it should be capped
+        let stepped = start.rotate_towards(target, Angle::from_degrees(10.0));
+        assert_similar!(start.angle_to(stepped).degrees(), 10.0, 0.01);
+        assert_similar!(stepped.angle_to(target).degrees(), 20.0, 0.01);
+        assert_similar!(stepped.vector.z, yaw(Angle::from_degrees(10.0)).vector.z, 0.0001);
+    }
 }